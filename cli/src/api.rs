@@ -1,320 +1,486 @@
 use anyhow::{Context, Result};
-use reqwest::Client;
+use reqwest::{Client, Method, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use crate::config::RequestPolicy;
 use crate::models::*;
 
-pub struct ApiClient {
-    client: Client,
-    base_url: String,
-    api_key: String,
+/// How long startup waits for the server before treating it as unreachable.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub type ApiResult<T> = std::result::Result<T, ApiError>;
+
+/// A failed API call, classified so callers can react differently to, say,
+/// a revoked key and a server that is briefly down.
+#[derive(Debug)]
+pub enum ApiError {
+    /// No response at all: DNS failure, refused connection, TLS error.
+    Network(reqwest::Error),
+    /// The server did not answer in time.
+    Timeout,
+    /// 401/403: the API key is missing, wrong or revoked.
+    Unauthorized { status: StatusCode, message: String },
+    /// 404: the project, deployment or database doesn't exist.
+    NotFound { message: String },
+    /// 409: the request clashes with the current state on the server.
+    Conflict { message: String },
+    /// 400/422: the server rejected the request as invalid.
+    Validation { message: String },
+    /// 5xx, or any other status the client doesn't expect.
+    Server { status: StatusCode, message: String },
+    /// The server answered with success but the body couldn't be read.
+    Decode(reqwest::Error),
 }
 
-impl ApiClient {
-    pub fn new(base_url: String, api_key: String) -> Self {
-        Self {
-            client: Client::new(),
-            base_url: base_url.trim_end_matches('/').to_string(),
-            api_key,
+/// Error payload returned by the backend: `{"error": "..."}`.
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl ApiError {
+    async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+
+        let message = match serde_json::from_str::<ErrorBody>(&body) {
+            Ok(parsed) => parsed.error,
+            Err(_) if !body.trim().is_empty() => body.trim().to_string(),
+            Err(_) => status.canonical_reason().unwrap_or("no details").to_string(),
+        };
+
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ApiError::Unauthorized { status, message },
+            StatusCode::NOT_FOUND => ApiError::NotFound { message },
+            StatusCode::CONFLICT => ApiError::Conflict { message },
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => ApiError::Validation { message },
+            _ => ApiError::Server { status, message },
         }
     }
 
-    pub async fn validate_connection(&self) -> Result<User> {
-        let url = format!("{}/api/user", self.base_url);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to connect to server")?;
+    pub fn is_auth(&self) -> bool {
+        matches!(self, ApiError::Unauthorized { .. })
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Authentication failed: {}", response.status());
+    /// Failures that may go away on their own, as opposed to ones caused by
+    /// the request or the credentials.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ApiError::Network(_) | ApiError::Timeout => true,
+            ApiError::Server { status, .. } => status.is_server_error(),
+            _ => false,
         }
-
-        let user = response.json::<User>().await
-            .context("Failed to parse user response")?;
-
-        Ok(user)
     }
 
-    pub async fn list_projects(&self) -> Result<Vec<Project>> {
-        let url = format!("{}/api/projects", self.base_url);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to fetch projects")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch projects: {}", response.status());
+    /// The error with a hint about what to do next, for the TUI's error line.
+    pub fn describe(&self, action: &str) -> String {
+        if self.is_auth() {
+            format!("{}: {}. Reconfigure the profile from Settings (s).", action, self)
+        } else if self.is_transient() {
+            format!("{}: {}. The server may be briefly unavailable, try again.", action, self)
+        } else {
+            format!("{}: {}", action, self)
         }
-
-        let projects = response.json::<Vec<Project>>().await
-            .context("Failed to parse projects response")?;
-
-        Ok(projects)
     }
+}
 
-    pub async fn get_project(&self, id: &str) -> Result<Project> {
-        let url = format!("{}/api/projects/{}", self.base_url, id);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to fetch project")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch project: {}", response.status());
+impl From<reqwest::Error> for ApiError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            ApiError::Timeout
+        } else if e.is_decode() {
+            ApiError::Decode(e)
+        } else {
+            ApiError::Network(e)
         }
-
-        let project = response.json::<Project>().await
-            .context("Failed to parse project response")?;
-
-        Ok(project)
     }
+}
 
-    pub async fn create_project(&self, request: CreateProjectRequest) -> Result<Project> {
-        let url = format!("{}/api/projects", self.base_url);
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", &self.api_key)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to create project")?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to create project: {}", error_text);
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(e) => write!(f, "Failed to connect to server: {}", root_cause(e)),
+            ApiError::Timeout => write!(f, "Server did not respond in time"),
+            ApiError::Unauthorized { status, message } => write!(f, "Authentication failed ({}): {}", status.as_u16(), message),
+            ApiError::NotFound { message } => write!(f, "Not found: {}", message),
+            ApiError::Conflict { message } => write!(f, "Conflict: {}", message),
+            ApiError::Validation { message } => write!(f, "Invalid request: {}", message),
+            ApiError::Server { status, message } => write!(f, "Server error ({}): {}", status.as_u16(), message),
+            ApiError::Decode(e) => write!(f, "Unexpected response from server: {}", root_cause(e)),
         }
+    }
+}
 
-        let project = response.json::<Project>().await
-            .context("Failed to parse project response")?;
+impl std::error::Error for ApiError {}
 
-        Ok(project)
+/// The innermost error, which for connection failures is the useful part
+/// ("Connection refused" rather than "error sending request").
+fn root_cause<'a>(e: &'a (dyn std::error::Error + 'static)) -> &'a (dyn std::error::Error + 'static) {
+    let mut cause = e;
+    while let Some(source) = cause.source() {
+        cause = source;
     }
+    cause
+}
 
-    pub async fn redeploy_project(&self, id: &str, commit: Option<String>) -> Result<serde_json::Value> {
-        let url = format!("{}/api/projects/{}/redeploy", self.base_url, id);
-        
-        let request = RedeployRequest { commit };
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", &self.api_key)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to redeploy project")?;
+/// Turns a non-success response into the matching `ApiError`.
+async fn check(response: Response) -> ApiResult<Response> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to redeploy project: {}", response.status());
+#[derive(Clone)]
+pub struct ApiClient {
+    client: Client,
+    base_url: String,
+    api_key: String,
+    policy: RequestPolicy,
+}
+
+impl ApiClient {
+    pub fn new(base_url: String, api_key: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            policy: RequestPolicy::default(),
         }
+    }
 
-        let result = response.json::<serde_json::Value>().await
-            .context("Failed to parse redeploy response")?;
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
 
-        Ok(result)
+    /// Sends a request, retrying GET and PUT on transient failures. Other
+    /// methods may have taken effect even when the response was lost, so
+    /// they are only attempted once.
+    async fn send<B: Serialize + ?Sized>(&self, method: Method, path: &str, body: Option<&B>) -> ApiResult<Response> {
+        let idempotent = method == Method::GET || method == Method::PUT;
+        let mut attempt = 0;
+
+        loop {
+            match self.send_once(method.clone(), path, body, self.policy.timeout()).await {
+                Err(e) if idempotent && e.is_transient() && attempt < self.policy.retries => {
+                    tokio::time::sleep(self.policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
     }
 
-    pub async fn stop_project(&self, id: &str) -> Result<serde_json::Value> {
-        let url = format!("{}/api/projects/{}/stop", self.base_url, id);
-        
-        let response = self.client
-            .post(&url)
+    async fn send_once<B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        timeout: Duration,
+    ) -> ApiResult<Response> {
+        let mut request = self.client
+            .request(method, format!("{}{}", self.base_url, path))
             .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to stop project")?;
+            .timeout(timeout);
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to stop project: {}", response.status());
+        if let Some(body) = body {
+            request = request.json(body);
         }
 
-        let result = response.json::<serde_json::Value>().await
-            .context("Failed to parse stop response")?;
+        check(request.send().await?).await
+    }
 
-        Ok(result)
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> ApiResult<T> {
+        let response = self.send(Method::GET, path, None::<&()>).await?;
+        Ok(response.json().await?)
     }
 
-    pub async fn get_activity(&self) -> Result<Vec<Deployment>> {
-        let url = format!("{}/api/activity", self.base_url);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to fetch activity")?;
+    async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(&self, path: &str, body: Option<&B>) -> ApiResult<T> {
+        let response = self.send(Method::POST, path, body).await?;
+        Ok(response.json().await?)
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch activity: {}", response.status());
-        }
+    pub async fn validate_connection(&self) -> ApiResult<User> {
+        // Not retried: at startup the user decides whether to try again
+        let response = self.send_once(Method::GET, "/api/user", None::<&()>, CONNECT_TIMEOUT).await?;
+        Ok(response.json().await?)
+    }
 
-        let deployments = response.json::<Vec<Deployment>>().await
-            .context("Failed to parse activity response")?;
+    pub async fn list_projects(&self) -> ApiResult<Vec<Project>> {
+        self.get("/api/projects").await
+    }
 
-        Ok(deployments)
+    pub async fn get_project(&self, id: &str) -> ApiResult<Project> {
+        self.get(&format!("/api/projects/{}", id)).await
     }
 
-    // Storage API methods
-    pub async fn get_storage_stats(&self) -> Result<StorageStats> {
-        let url = format!("{}/api/storage/stats", self.base_url);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to fetch storage stats")?;
+    pub async fn create_project(&self, request: CreateProjectRequest) -> ApiResult<Project> {
+        self.post("/api/projects", Some(&request)).await
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch storage stats: {}", response.status());
-        }
+    /// Overwrites the name, repository, runtime and commands of a project.
+    pub async fn update_project(&self, id: &str, request: UpdateProjectRequest) -> ApiResult<Project> {
+        let response = self.send(Method::PUT, &format!("/api/projects/{}", id), Some(&request)).await?;
+        Ok(response.json().await?)
+    }
 
-        let stats = response.json::<StorageStats>().await
-            .context("Failed to parse storage stats response")?;
+    /// Replaces the full set of environment variables of a project.
+    pub async fn update_project_env(&self, id: &str, env_vars: std::collections::HashMap<String, String>) -> ApiResult<()> {
+        let request = UpdateEnvRequest { env_vars };
+        self.send(Method::PUT, &format!("/api/projects/{}/env", id), Some(&request)).await?;
+        Ok(())
+    }
 
-        Ok(stats)
+    pub async fn redeploy_project(&self, id: &str, commit: Option<String>) -> ApiResult<serde_json::Value> {
+        let request = RedeployRequest { commit };
+        self.post(&format!("/api/projects/{}/redeploy", id), Some(&request)).await
     }
 
-    pub async fn list_databases(&self) -> Result<Vec<Database>> {
-        let url = format!("{}/api/storage/databases", self.base_url);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to fetch databases")?;
+    pub async fn stop_project(&self, id: &str) -> ApiResult<serde_json::Value> {
+        self.post(&format!("/api/projects/{}/stop", id), None::<&()>).await
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch databases: {}", response.status());
-        }
+    pub async fn get_activity(&self) -> ApiResult<Vec<Deployment>> {
+        self.get("/api/activity").await
+    }
 
-        let databases = response.json::<Vec<Database>>().await
-            .context("Failed to parse databases response")?;
+    // Storage API methods
+    pub async fn get_storage_stats(&self) -> ApiResult<StorageStats> {
+        self.get("/api/storage/stats").await
+    }
 
-        Ok(databases)
+    pub async fn list_databases(&self) -> ApiResult<Vec<Database>> {
+        self.get("/api/storage/databases").await
     }
 
-    pub async fn create_database(&self, name: String, db_type: String) -> Result<serde_json::Value> {
-        let url = format!("{}/api/storage/databases", self.base_url);
-        
+    pub async fn create_database(&self, name: String, db_type: String) -> ApiResult<serde_json::Value> {
         let request = CreateDatabaseRequest { name, db_type };
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", &self.api_key)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to create database")?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to create database: {}", error_text);
-        }
+        self.post("/api/storage/databases", Some(&request)).await
+    }
 
-        let result = response.json::<serde_json::Value>().await
-            .context("Failed to parse create database response")?;
+    pub async fn delete_database(&self, id: u32) -> ApiResult<()> {
+        self.send(Method::DELETE, &format!("/api/storage/databases/{}", id), None::<&()>).await?;
+        Ok(())
+    }
 
-        Ok(result)
+    pub async fn get_database_credentials(&self, id: u32) -> ApiResult<DatabaseCredentials> {
+        self.get(&format!("/api/storage/databases/{}/credentials", id)).await
     }
 
-    pub async fn delete_database(&self, id: u32) -> Result<()> {
-        let url = format!("{}/api/storage/databases/{}", self.base_url, id);
-        
-        let response = self.client
-            .delete(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to delete database")?;
+    pub async fn update_database_credentials(&self, id: u32, username: String, password: String) -> ApiResult<()> {
+        let request = UpdateDatabaseCredentialsRequest { username, password };
+        self.send(Method::PUT, &format!("/api/storage/databases/{}/credentials", id), Some(&request)).await?;
+        Ok(())
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to delete database: {}", response.status());
-        }
+    pub async fn stop_database(&self, id: u32) -> ApiResult<()> {
+        self.send(Method::POST, &format!("/api/storage/databases/{}/stop", id), None::<&()>).await?;
+        Ok(())
+    }
 
+    pub async fn restart_database(&self, id: u32) -> ApiResult<()> {
+        self.send(Method::POST, &format!("/api/storage/databases/{}/restart", id), None::<&()>).await?;
         Ok(())
     }
 
-    pub async fn get_database_credentials(&self, id: u32) -> Result<DatabaseCredentials> {
-        let url = format!("{}/api/storage/databases/{}/credentials", self.base_url, id);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to fetch database credentials")?;
+    /// URL of the log websocket for a deployment, keeping any path prefix
+    /// the server is mounted under.
+    fn log_stream_url(&self, deployment_id: &str) -> Result<Url> {
+        let mut url = Url::parse(&self.base_url)
+            .with_context(|| format!("Invalid server URL '{}'", self.base_url))?;
+
+        let scheme = match url.scheme() {
+            "https" | "wss" => "wss",
+            "http" | "ws" => "ws",
+            other => anyhow::bail!("Unsupported server URL scheme '{}'", other),
+        };
+        url.set_scheme(scheme)
+            .map_err(|_| anyhow::anyhow!("Cannot derive websocket URL from '{}'", self.base_url))?;
+
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Cannot derive websocket URL from '{}'", self.base_url))?
+            .pop_if_empty()
+            .extend(["api", "deployments", deployment_id, "logs", "stream"]);
+
+        Ok(url)
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch database credentials: {}", response.status());
+    /// Forwards build output of a deployment from the log websocket into `tx`,
+    /// reconnecting with backoff when the connection drops. Runs until the
+    /// receiver is dropped or reconnecting keeps failing.
+    pub async fn stream_logs(&self, deployment_id: &str, tx: mpsc::UnboundedSender<LogEvent>) -> Result<()> {
+        use futures_util::StreamExt;
+        use tokio_tungstenite::tungstenite::{client::IntoClientRequest, http::HeaderValue, Error as WsError, Message};
+
+        let url = self.log_stream_url(deployment_id)?;
+        let mut failures = 0;
+
+        let _ = tx.send(LogEvent::State(StreamState::Connecting));
+
+        loop {
+            let mut request = url.as_str().into_client_request()?;
+            request.headers_mut().insert("Authorization", HeaderValue::from_str(&self.api_key)?);
+
+            match tokio_tungstenite::connect_async(request).await {
+                Ok((ws_stream, _)) => {
+                    let opened = Instant::now();
+                    let mut delivered = false;
+                    if tx.send(LogEvent::State(StreamState::Live)).is_err() {
+                        return Ok(());
+                    }
+
+                    let (_, mut read) = ws_stream.split();
+                    while let Some(Ok(msg)) = read.next().await {
+                        let text = match msg {
+                            Message::Text(text) => text,
+                            Message::Close(_) => break,
+                            _ => continue,
+                        };
+                        delivered = true;
+                        if tx.send(LogEvent::Chunk(text.to_string())).is_err() {
+                            return Ok(());
+                        }
+                    }
+
+                    // A server that accepts and then drops us right away
+                    // counts as failing, or we'd reconnect forever
+                    if delivered || opened.elapsed() >= STABLE_STREAM {
+                        failures = 0;
+                    } else {
+                        failures += 1;
+                    }
+                }
+                Err(WsError::Http(response)) if response.status().is_client_error() => {
+                    let _ = tx.send(LogEvent::State(StreamState::Closed));
+                    anyhow::bail!("Log stream rejected: {}", response.status());
+                }
+                Err(_) => failures += 1,
+            }
+
+            if failures > MAX_STREAM_RECONNECTS {
+                let _ = tx.send(LogEvent::State(StreamState::Closed));
+                anyhow::bail!("Log stream unavailable after {} attempts", failures);
+            }
+
+            if tx.send(LogEvent::State(StreamState::Reconnecting)).is_err() {
+                return Ok(());
+            }
+            tokio::time::sleep(self.policy.backoff(failures)).await;
         }
+    }
+}
 
-        let credentials = response.json::<DatabaseCredentials>().await
-            .context("Failed to parse database credentials response")?;
+/// Connection state of a log stream, shown in the logs header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamState {
+    Connecting,
+    Live,
+    Reconnecting,
+    Closed,
+}
 
-        Ok(credentials)
+#[derive(Debug)]
+pub enum LogEvent {
+    State(StreamState),
+    Chunk(String),
+}
+
+/// Consecutive failed connection attempts before a log stream gives up.
+const MAX_STREAM_RECONNECTS: u32 = 10;
+
+/// How long a log stream connection must stay up, without sending anything,
+/// before it counts as working again.
+const STABLE_STREAM: Duration = Duration::from_secs(30);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::SinkExt;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+
+    fn client(base_url: &str) -> ApiClient {
+        ApiClient::new(base_url.to_string(), "key".to_string()).with_policy(RequestPolicy {
+            backoff_ms: 1,
+            max_backoff_ms: 1,
+            ..RequestPolicy::default()
+        })
     }
 
-    pub async fn update_database_credentials(&self, id: u32, username: String, password: String) -> Result<()> {
-        let url = format!("{}/api/storage/databases/{}/credentials", self.base_url, id);
-        
-        let request = UpdateDatabaseCredentialsRequest { username, password };
-        
-        let response = self.client
-            .put(&url)
-            .header("Authorization", &self.api_key)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to update database credentials")?;
+    #[test]
+    fn log_stream_url_follows_scheme_and_prefix() {
+        let url = client("http://localhost:8080").log_stream_url("d1").unwrap();
+        assert_eq!(url.as_str(), "ws://localhost:8080/api/deployments/d1/logs/stream");
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to update database credentials: {}", response.status());
-        }
+        let url = client("https://example.com/deploy/").log_stream_url("d1").unwrap();
+        assert_eq!(url.as_str(), "wss://example.com/deploy/api/deployments/d1/logs/stream");
 
-        Ok(())
+        assert!(client("ftp://example.com").log_stream_url("d1").is_err());
     }
 
-    pub async fn stop_database(&self, id: u32) -> Result<()> {
-        let url = format!("{}/api/storage/databases/{}/stop", self.base_url, id);
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to stop database")?;
+    /// Serves one websocket connection per entry of `sessions`, sending its
+    /// chunks and closing, then drops every further connection unanswered.
+    async fn serve(sessions: Vec<Vec<&'static str>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for chunks in sessions {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                for chunk in chunks {
+                    ws.send(Message::Text(chunk.into())).await.unwrap();
+                }
+                let _ = ws.close(None).await;
+            }
+            while let Ok((stream, _)) = listener.accept().await {
+                drop(stream);
+            }
+        });
+        format!("http://{}", addr)
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to stop database: {}", response.status());
+    async fn stream(base_url: &str) -> (Vec<String>, Vec<StreamState>, Result<()>) {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let result = tokio::time::timeout(Duration::from_secs(10), client(base_url).stream_logs("d1", tx))
+            .await
+            .expect("stream kept reconnecting");
+
+        let (mut chunks, mut states) = (Vec::new(), Vec::new());
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                LogEvent::Chunk(chunk) => chunks.push(chunk),
+                LogEvent::State(state) => states.push(state),
+            }
         }
-
-        Ok(())
+        (chunks, states, result)
     }
 
-    pub async fn restart_database(&self, id: u32) -> Result<()> {
-        let url = format!("{}/api/storage/databases/{}/restart", self.base_url, id);
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to restart database")?;
+    #[tokio::test]
+    async fn reconnect_keeps_repeated_lines() {
+        // The server only sends new output, so a line that shows up again
+        // after reconnecting was really printed twice
+        let url = serve(vec![vec!["npm ci\n", "retrying\n"], vec!["retrying\n", "done\n"]]).await;
+        let (chunks, states, result) = stream(&url).await;
+
+        assert_eq!(chunks, ["npm ci\n", "retrying\n", "retrying\n", "done\n"]);
+        assert_eq!(states[..4], [StreamState::Connecting, StreamState::Live, StreamState::Reconnecting, StreamState::Live]);
+        assert_eq!(states.last(), Some(&StreamState::Closed));
+        assert!(result.is_err());
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to restart database: {}", response.status());
-        }
+    #[tokio::test]
+    async fn connections_dropped_at_once_run_out_of_retries() {
+        let url = serve(vec![vec![]; MAX_STREAM_RECONNECTS as usize + 5]).await;
+        let (chunks, states, result) = stream(&url).await;
 
-        Ok(())
+        assert!(chunks.is_empty());
+        let live = states.iter().filter(|&&s| s == StreamState::Live).count();
+        assert_eq!(live, MAX_STREAM_RECONNECTS as usize + 1);
+        assert!(result.unwrap_err().to_string().contains("unavailable"));
     }
 }
@@ -0,0 +1,76 @@
+use crate::config::{get_config_path, write_private};
+use crate::models::{Database, Deployment, Project, StorageStats};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Last data fetched from a server, shown when the TUI starts in offline mode.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub saved_at: String,
+    pub projects: Vec<Project>,
+    pub activity: Vec<Deployment>,
+    pub databases: Vec<Database>,
+    pub storage_stats: Option<StorageStats>,
+}
+
+fn cache_path(profile: &str) -> Result<PathBuf> {
+    let dir = get_config_path()?
+        .parent()
+        .context("Failed to get config directory")?
+        .join("cache");
+
+    fs::create_dir_all(&dir)
+        .context("Failed to create cache directory")?;
+
+    Ok(dir.join(format!("{}.json", profile)))
+}
+
+pub fn load(profile: &str) -> Option<Snapshot> {
+    let contents = fs::read_to_string(cache_path(profile).ok()?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+pub fn save(profile: &str, mut snapshot: Snapshot) -> Result<()> {
+    // Environment variables, webhook secrets and tokens embedded in repo
+    // URLs are secrets; don't keep them around on disk. Logs can still echo
+    // them, so the file is private to the user as well.
+    for project in &mut snapshot.projects {
+        project.env_vars = None;
+        project.webhook_secret.clear();
+        project.repo_url = strip_credentials(&project.repo_url);
+    }
+
+    let contents = serde_json::to_string(&snapshot)
+        .context("Failed to serialize cache")?;
+
+    write_private(&cache_path(profile)?, contents.as_bytes())
+        .context("Failed to write cache file")?;
+
+    Ok(())
+}
+
+/// `url` without a username or password, e.g. a git token in
+/// `https://<token>@github.com/...`.
+fn strip_credentials(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(mut parsed) if !parsed.username().is_empty() || parsed.password().is_some() => {
+            let _ = parsed.set_username("");
+            let _ = parsed.set_password(None);
+            parsed.to_string()
+        }
+        _ => url.to_string(),
+    }
+}
+
+pub fn delete(profile: &str) -> Result<()> {
+    let path = cache_path(profile)?;
+
+    if path.exists() {
+        fs::remove_file(&path)
+            .context("Failed to delete cache file")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use crate::output::OutputFormat;
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Command line interface for Clickploy.
+///
+/// Running without a subcommand opens the interactive terminal UI.
+#[derive(Debug, Parser)]
+#[command(name = "clickploy", version, about)]
+pub struct Cli {
+    /// Output format for listings and records
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+
+    /// Server profile to use instead of the current context
+    #[arg(long, global = true, env = "CLICKPLOY_PROFILE")]
+    pub profile: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Manage projects
+    #[command(subcommand)]
+    Projects(ProjectsCommand),
+    /// Trigger a redeploy of a project
+    Deploy(DeployArgs),
+    /// Stop a project's running container
+    Stop(StopArgs),
+    /// Manage databases
+    #[command(subcommand)]
+    Db(DbCommand),
+    /// Show recent deployment activity
+    Activity,
+    /// Print the build log of a deployment
+    Logs(LogsArgs),
+    /// Sync environment variables with a dotenv file
+    #[command(subcommand)]
+    Env(EnvCommand),
+    /// Manage server profiles
+    #[command(subcommand)]
+    Context(ContextCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProjectsCommand {
+    /// List all projects
+    List,
+    /// Show a single project
+    Get {
+        /// Project name or ID
+        project: String,
+    },
+    /// Create a new project and start its first deployment
+    Create(CreateProjectArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CreateProjectArgs {
+    /// Project name
+    #[arg(long)]
+    pub name: String,
+    /// Git repository URL
+    #[arg(long)]
+    pub repo: String,
+    /// Port the application listens on
+    #[arg(long, default_value_t = 3000)]
+    pub port: i32,
+    /// Access token for private repositories
+    #[arg(long)]
+    pub git_token: Option<String>,
+    /// Environment variable in KEY=VALUE form (repeatable)
+    #[arg(long = "env", value_name = "KEY=VALUE")]
+    pub env_vars: Vec<String>,
+    #[arg(long)]
+    pub install_command: Option<String>,
+    #[arg(long)]
+    pub build_command: Option<String>,
+    #[arg(long)]
+    pub start_command: Option<String>,
+    /// Runtime override (defaults to auto-detection)
+    #[arg(long)]
+    pub runtime: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct DeployArgs {
+    /// Project name or ID
+    pub project: String,
+    /// Deploy a specific commit instead of the branch head
+    #[arg(long)]
+    pub commit: Option<String>,
+    /// Stream the build log and wait until the deployment is live or failed.
+    ///
+    /// Exits with 0 when live, 2 when the build fails and 3 on timeout.
+    #[arg(long)]
+    pub wait: bool,
+    /// Seconds to wait before giving up (with --wait)
+    #[arg(long, default_value_t = 900, requires = "wait")]
+    pub timeout: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct LogsArgs {
+    /// Project name or ID (uses its latest deployment), or a deployment ID
+    pub target: String,
+    /// Keep streaming new output until the deployment finishes
+    #[arg(short, long)]
+    pub follow: bool,
+    /// When following a build that was already running, also print the
+    /// output produced before attaching once the build finishes
+    #[arg(long, requires = "follow")]
+    pub since_start: bool,
+    /// Prefix each streamed line with the time it was received
+    #[arg(short, long)]
+    pub timestamps: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EnvCommand {
+    /// Write a project's variables to a dotenv file
+    Pull {
+        /// Project name or ID
+        project: String,
+        #[arg(long, default_value = ".env")]
+        file: PathBuf,
+        /// Overwrite an existing file without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Replace a project's variables with the contents of a dotenv file
+    Push {
+        /// Project name or ID
+        project: String,
+        #[arg(long, default_value = ".env")]
+        file: PathBuf,
+        /// Apply without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+        /// Show values in the preview instead of only keys
+        #[arg(long)]
+        show_values: bool,
+    },
+    /// Compare a dotenv file against the server
+    Diff {
+        /// Project name or ID
+        project: String,
+        #[arg(long, default_value = ".env")]
+        file: PathBuf,
+        /// Show values instead of only keys
+        #[arg(long)]
+        show_values: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ContextCommand {
+    /// List configured profiles
+    List,
+    /// Make a profile the current context
+    Use {
+        name: String,
+    },
+    /// Add a profile, or replace an existing one with the same name
+    Add {
+        name: String,
+        /// Server URL, e.g. https://deploy.example.com
+        #[arg(long)]
+        server: String,
+        /// API key from <server>/settings/session; asked for when omitted
+        #[arg(long, env = "CLICKPLOY_API_KEY", hide_env_values = true)]
+        api_key: Option<String>,
+        /// Switch to the new profile
+        #[arg(long = "use")]
+        make_current: bool,
+        /// Save without checking that the server accepts the key
+        #[arg(long)]
+        no_verify: bool,
+    },
+    /// Remove a profile
+    Remove {
+        name: String,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct StopArgs {
+    /// Project name or ID
+    pub project: String,
+    /// Stop without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// List all databases
+    List,
+    /// Show disk usage
+    Stats,
+    /// Create a new database
+    Create {
+        name: String,
+        /// Database type
+        #[arg(long = "type", default_value = "sqlite", value_parser = ["sqlite", "mongodb"])]
+        db_type: String,
+    },
+    /// Delete a database and all of its data
+    Delete {
+        /// Database name or ID
+        database: String,
+        /// Delete without typing the name to confirm
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Stop a database
+    Stop {
+        /// Database name or ID
+        database: String,
+        /// Stop without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Restart a database
+    Restart {
+        /// Database name or ID
+        database: String,
+        /// Restart without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Show connection credentials
+    Credentials {
+        /// Database name or ID
+        database: String,
+    },
+    /// Change the username and password of a database
+    SetCredentials {
+        /// Database name or ID
+        database: String,
+        #[arg(long)]
+        username: String,
+        #[arg(long)]
+        password: String,
+        /// Change them without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
@@ -0,0 +1,8 @@
+use crate::api::ApiClient;
+use crate::output::{print_list, OutputFormat};
+use anyhow::Result;
+
+pub async fn run(client: &ApiClient, output: OutputFormat) -> Result<()> {
+    let deployments = client.get_activity().await?;
+    print_list(output, &deployments)
+}
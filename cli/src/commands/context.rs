@@ -0,0 +1,90 @@
+use crate::api::ApiClient;
+use crate::cache;
+use crate::cli::ContextCommand;
+use crate::config::{config_exists, load_config, save_config, Config, Profile};
+use crate::output::{print_list, OutputFormat, Tabular};
+use crate::prompt;
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct ContextEntry {
+    name: String,
+    server_url: String,
+    current: bool,
+}
+
+impl Tabular for ContextEntry {
+    fn headers() -> &'static [&'static str] {
+        &["CURRENT", "NAME", "SERVER"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            if self.current { "*".to_string() } else { String::new() },
+            self.name.clone(),
+            self.server_url.clone(),
+        ]
+    }
+}
+
+pub async fn run(command: ContextCommand, output: OutputFormat) -> Result<()> {
+    let mut config = if config_exists() {
+        load_config()?
+    } else {
+        Config::default()
+    };
+
+    match command {
+        ContextCommand::List => {
+            let entries: Vec<ContextEntry> = config
+                .profiles
+                .iter()
+                .map(|(name, profile)| ContextEntry {
+                    name: name.clone(),
+                    server_url: profile.server_url.clone(),
+                    current: *name == config.current_context,
+                })
+                .collect();
+            print_list(output, &entries)?;
+        }
+        ContextCommand::Use { name } => {
+            config.use_profile(&name)?;
+            save_config(&config)?;
+            eprintln!("Switched to {}", name);
+        }
+        ContextCommand::Add { name, server, api_key, make_current, no_verify } => {
+            let server_url = server.trim_end_matches('/').to_string();
+            // Keys passed on the command line end up in shell history
+            let api_key = match api_key {
+                Some(key) => key,
+                None => prompt::secret("API key")
+                    .context("Pass --api-key or set CLICKPLOY_API_KEY")?,
+            };
+            if api_key.trim().is_empty() {
+                anyhow::bail!("The API key can't be empty");
+            }
+
+            if !no_verify {
+                let client = ApiClient::new(server_url.clone(), api_key.clone());
+                let user = client.validate_connection().await?;
+                eprintln!("Authenticated as {} ({})", user.name, user.email);
+            }
+
+            config.add_profile(&name, Profile { server_url, api_key });
+            if make_current {
+                config.use_profile(&name)?;
+            }
+            save_config(&config)?;
+            eprintln!("Saved profile {}", name);
+        }
+        ContextCommand::Remove { name } => {
+            config.remove_profile(&name)?;
+            save_config(&config)?;
+            cache::delete(&name)?;
+            eprintln!("Removed profile {}", name);
+        }
+    }
+
+    Ok(())
+}
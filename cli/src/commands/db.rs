@@ -0,0 +1,70 @@
+use crate::api::ApiClient;
+use crate::cli::DbCommand;
+use crate::commands::resolve_database;
+use crate::output::{print_item, print_list, OutputFormat};
+use crate::prompt::{confirm, confirm_name};
+use anyhow::Result;
+
+pub async fn run(command: DbCommand, client: &ApiClient, output: OutputFormat) -> Result<()> {
+    match command {
+        DbCommand::List => {
+            let databases = client.list_databases().await?;
+            print_list(output, &databases)?;
+        }
+        DbCommand::Stats => {
+            let stats = client.get_storage_stats().await?;
+            print_item(output, &stats)?;
+        }
+        DbCommand::Create { name, db_type } => {
+            client.create_database(name.clone(), db_type.clone()).await?;
+            println!("Created {} database {}", db_type, name);
+        }
+        DbCommand::Delete { database, yes } => {
+            let db = resolve_database(client, &database).await?;
+            let question = format!("Delete database {} and all of its data?", db.name);
+            if !yes && !confirm_name(&question, &db.name)? {
+                anyhow::bail!("Aborted");
+            }
+            client.delete_database(db.id).await?;
+            println!("Deleted database {}", db.name);
+        }
+        DbCommand::Stop { database, yes } => {
+            let db = resolve_database(client, &database).await?;
+            if !yes && !confirm(&format!("Stop database {}?", db.name))? {
+                anyhow::bail!("Aborted");
+            }
+            client.stop_database(db.id).await?;
+            println!("Stopped database {}", db.name);
+        }
+        DbCommand::Restart { database, yes } => {
+            let db = resolve_database(client, &database).await?;
+            if !yes && !confirm(&format!("Restart database {}?", db.name))? {
+                anyhow::bail!("Aborted");
+            }
+            client.restart_database(db.id).await?;
+            println!("Restarted database {}", db.name);
+        }
+        DbCommand::Credentials { database } => {
+            let db = resolve_database(client, &database).await?;
+            if db.db_type == "sqlite" {
+                anyhow::bail!(
+                    "SQLite databases are file-based and don't have credentials (file: data/user_dbs/{}.db)",
+                    db.name
+                );
+            }
+            let creds = client.get_database_credentials(db.id).await?;
+            print_item(output, &creds)?;
+        }
+        DbCommand::SetCredentials { database, username, password, yes } => {
+            let db = resolve_database(client, &database).await?;
+            let question = format!("Change the credentials of {}? Apps using the old ones lose access.", db.name);
+            if !yes && !confirm(&question)? {
+                anyhow::bail!("Aborted");
+            }
+            client.update_database_credentials(db.id, username, password).await?;
+            println!("Updated credentials for {}", db.name);
+        }
+    }
+
+    Ok(())
+}
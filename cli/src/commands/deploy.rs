@@ -0,0 +1,83 @@
+use crate::api::ApiClient;
+use crate::cli::{DeployArgs, StopArgs};
+use crate::commands::logs::{follow, LogPrinter};
+use crate::commands::{resolve_project, EXIT_BUILD_FAILED, EXIT_TIMEOUT};
+use crate::prompt::confirm;
+use anyhow::Result;
+use std::time::Duration;
+
+pub async fn deploy(args: DeployArgs, client: &ApiClient) -> Result<()> {
+    let project = resolve_project(client, &args.project).await?;
+    let result = client.redeploy_project(&project.id, args.commit).await?;
+
+    let deployment_id = result
+        .get("deployment_id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    if !args.wait {
+        match deployment_id {
+            Some(id) => println!("Redeploy of {} started (deployment {})", project.name, id),
+            None => println!("Redeploy of {} started", project.name),
+        }
+        return Ok(());
+    }
+
+    // Older servers don't return the ID, fall back to the newest deployment
+    let deployment_id = match deployment_id {
+        Some(id) => id,
+        None => client
+            .get_project(&project.id)
+            .await?
+            .deployments
+            .and_then(|deps| deps.into_iter().next())
+            .map(|d| d.id)
+            .ok_or_else(|| anyhow::anyhow!("Redeploy started but no deployment was found"))?,
+    };
+
+    eprintln!("Waiting for deployment {} of {}...", deployment_id, project.name);
+
+    let code = wait_for_deployment(client, &project.id, &deployment_id, Duration::from_secs(args.timeout)).await?;
+    if code != 0 {
+        std::process::exit(code);
+    }
+
+    Ok(())
+}
+
+/// Streams the build log to stdout until the deployment reaches a terminal
+/// state. Returns the process exit code.
+async fn wait_for_deployment(client: &ApiClient, project_id: &str, deployment_id: &str, timeout: Duration) -> Result<i32> {
+    let mut printer = LogPrinter::new(false);
+    let followed = follow(client, project_id, deployment_id, Some(timeout), &mut printer).await?;
+
+    let Some(deployment) = followed.deployment else {
+        eprintln!("\nTimed out after {}s waiting for deployment {}", timeout.as_secs(), deployment_id);
+        return Ok(EXIT_TIMEOUT);
+    };
+
+    // The stream only carries output produced after we connected, so show
+    // the stored log if nothing came through it
+    if followed.streamed.is_empty() {
+        printer.print_stored(&deployment.logs)?;
+    }
+
+    if deployment.status == "live" {
+        eprintln!("\nDeployment {} is live", deployment.id);
+        Ok(0)
+    } else {
+        eprintln!("\nDeployment {} {}", deployment.id, deployment.status);
+        Ok(EXIT_BUILD_FAILED)
+    }
+}
+
+pub async fn stop(args: StopArgs, client: &ApiClient) -> Result<()> {
+    let project = resolve_project(client, &args.project).await?;
+    if !args.yes && !confirm(&format!("Stop {}?", project.name))? {
+        anyhow::bail!("Aborted");
+    }
+    client.stop_project(&project.id).await?;
+    println!("Stopped {}", project.name);
+
+    Ok(())
+}
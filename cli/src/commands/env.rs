@@ -0,0 +1,103 @@
+use crate::api::ApiClient;
+use crate::cli::EnvCommand;
+use crate::commands::resolve_project;
+use crate::dotenv::{self, EnvDiff, EnvMap};
+use crate::prompt::confirm;
+use anyhow::Result;
+
+pub async fn run(command: EnvCommand, client: &ApiClient) -> Result<()> {
+    match command {
+        EnvCommand::Pull { project, file, yes } => {
+            let (_, remote) = fetch(client, &project).await?;
+
+            if file.exists() && !yes {
+                let local = dotenv::read_file(&file).unwrap_or_default();
+                if local != remote && !confirm(&format!("Overwrite {}?", file.display()))? {
+                    anyhow::bail!("Aborted");
+                }
+            }
+
+            dotenv::write_file(&file, &remote)?;
+            eprintln!("Wrote {} variables to {}", remote.len(), file.display());
+        }
+        EnvCommand::Push { project, file, yes, show_values } => {
+            let local = dotenv::read_file(&file)?;
+            let (id, remote) = fetch(client, &project).await?;
+            let diff = EnvDiff::between(&remote, &local);
+
+            if diff.is_empty() {
+                eprintln!("Already up to date");
+                return Ok(());
+            }
+
+            print_diff(&diff, &remote, &local, show_values);
+
+            // The endpoint replaces the whole set, so removals happen implicitly
+            if !yes && !confirm(&format!("Apply these changes to {}?", project))? {
+                anyhow::bail!("Aborted");
+            }
+
+            client.update_project_env(&id, local.into_iter().collect()).await?;
+            eprintln!(
+                "Updated {}: {} added, {} changed, {} removed",
+                project,
+                diff.added.len(),
+                diff.changed.len(),
+                diff.removed.len()
+            );
+            eprintln!("Changes take effect on the next deploy");
+        }
+        EnvCommand::Diff { project, file, show_values } => {
+            let local = dotenv::read_file(&file)?;
+            let (_, remote) = fetch(client, &project).await?;
+            let diff = EnvDiff::between(&remote, &local);
+
+            if diff.is_empty() {
+                eprintln!("{} matches the server", file.display());
+            } else {
+                print_diff(&diff, &remote, &local, show_values);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn fetch(client: &ApiClient, ident: &str) -> Result<(String, EnvMap)> {
+    let project = resolve_project(client, ident).await?;
+    let project = client.get_project(&project.id).await?;
+
+    let vars = project
+        .env_vars
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| (e.key, e.value))
+        .collect();
+
+    Ok((project.id, vars))
+}
+
+/// Prints what applying `local` over `remote` would change.
+fn print_diff(diff: &EnvDiff, remote: &EnvMap, local: &EnvMap, show_values: bool) {
+    for key in &diff.added {
+        if show_values {
+            println!("+ {}={}", key, local[key]);
+        } else {
+            println!("+ {}", key);
+        }
+    }
+    for key in &diff.changed {
+        if show_values {
+            println!("~ {}: {} -> {}", key, remote[key], local[key]);
+        } else {
+            println!("~ {}", key);
+        }
+    }
+    for key in &diff.removed {
+        if show_values {
+            println!("- {}={}", key, remote[key]);
+        } else {
+            println!("- {}", key);
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use crate::api::{ApiClient, LogEvent, StreamState};
+use crate::cli::LogsArgs;
+use crate::models::Deployment;
+use anyhow::Result;
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Amount of streamed text used to locate the live output inside the final stored log.
+const ANCHOR_LEN: usize = 200;
+
+pub async fn run(args: LogsArgs, client: &ApiClient) -> Result<()> {
+    let (project_id, deployment) = resolve_deployment(client, &args.target).await?;
+    let mut printer = LogPrinter::new(args.timestamps);
+
+    printer.print_stored(&deployment.logs)?;
+
+    if !args.follow {
+        return Ok(());
+    }
+
+    if deployment.status != "building" {
+        eprintln!("Deployment {} is {}, no further output will be produced", deployment.id, deployment.status);
+        return Ok(());
+    }
+
+    let followed = follow(client, &project_id, &deployment.id, None, &mut printer).await?;
+    let Some(finished) = followed.deployment else {
+        return Ok(());
+    };
+
+    // The server only stores the full log once the build is over, and the
+    // stream only carries output produced after we attached
+    match split_around(&finished.logs, &followed.streamed) {
+        Some((before, after)) => {
+            if !before.is_empty() {
+                if args.since_start {
+                    eprintln!("--- output before attaching ---");
+                    printer.print_stored(before)?;
+                    eprintln!("--- end of earlier output ---");
+                } else {
+                    eprintln!("({} bytes of output from before attaching omitted, use --since-start to show them)", before.len());
+                }
+            }
+            printer.print_stored(after)?;
+        }
+        // The server drops output when a stream falls behind, so what came
+        // in can't be placed; show everything rather than lose the end
+        None => {
+            eprintln!("--- stream incomplete, full log follows ---");
+            printer.print_stored(&finished.logs)?;
+        }
+    }
+
+    eprintln!("Deployment {} {}", finished.id, finished.status);
+
+    Ok(())
+}
+
+/// Resolves a project (by name or ID) to its latest deployment, or a deployment ID directly.
+pub async fn resolve_deployment(client: &ApiClient, target: &str) -> Result<(String, Deployment)> {
+    let projects = client.list_projects().await?;
+
+    if let Some(project) = projects.iter().find(|p| p.id == target || p.name == target) {
+        let latest = project
+            .deployments
+            .as_ref()
+            .and_then(|deps| deps.first())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Project '{}' has no deployments", project.name))?;
+        return Ok((project.id.clone(), latest));
+    }
+
+    projects
+        .iter()
+        .flat_map(|p| p.deployments.iter().flatten())
+        .find(|d| d.id == target)
+        .map(|d| (d.project_id.clone(), d.clone()))
+        .ok_or_else(|| anyhow::anyhow!("No project or deployment named '{}'", target))
+}
+
+/// Writes log output to stdout, optionally prefixing each line with the time it arrived.
+pub struct LogPrinter {
+    out: Stdout,
+    timestamps: bool,
+    at_line_start: bool,
+}
+
+impl LogPrinter {
+    pub fn new(timestamps: bool) -> Self {
+        Self {
+            out: io::stdout(),
+            timestamps,
+            at_line_start: true,
+        }
+    }
+
+    /// Prints live output, stamped with the receive time when enabled.
+    pub fn print(&mut self, text: &str) -> io::Result<()> {
+        if !self.timestamps {
+            self.out.write_all(text.as_bytes())?;
+            return self.out.flush();
+        }
+
+        let stamp = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z").to_string();
+        for piece in text.split_inclusive('\n') {
+            if self.at_line_start {
+                write!(self.out, "{} ", stamp)?;
+            }
+            self.out.write_all(piece.as_bytes())?;
+            self.at_line_start = piece.ends_with('\n');
+        }
+        self.out.flush()
+    }
+
+    /// Prints stored output, which has no receive time, terminated by a newline.
+    pub fn print_stored(&mut self, text: &str) -> io::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        if !self.at_line_start {
+            writeln!(self.out)?;
+        }
+        self.out.write_all(text.as_bytes())?;
+        if !text.ends_with('\n') {
+            writeln!(self.out)?;
+        }
+        self.at_line_start = true;
+        self.out.flush()
+    }
+}
+
+pub struct Followed {
+    /// The deployment in its terminal state, or `None` on timeout.
+    pub deployment: Option<Deployment>,
+    /// Everything received over the live stream.
+    pub streamed: String,
+}
+
+/// Streams a deployment's build output into `printer` while polling the
+/// project until the deployment is live, failed or stopped.
+pub async fn follow(
+    client: &ApiClient,
+    project_id: &str,
+    deployment_id: &str,
+    timeout: Option<Duration>,
+    printer: &mut LogPrinter,
+) -> Result<Followed> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<LogEvent>();
+    let stream_client = client.clone();
+    let stream_id = deployment_id.to_string();
+    let stream_task = tokio::spawn(async move {
+        let _ = stream_client.stream_logs(&stream_id, tx).await;
+    });
+
+    let deadline = timeout.map(|t| tokio::time::Instant::now() + t);
+    let mut poll = tokio::time::interval(POLL_INTERVAL);
+    let mut streamed = String::new();
+
+    let deployment = loop {
+        tokio::select! {
+            Some(event) = rx.recv() => match event {
+                LogEvent::Chunk(chunk) => {
+                    printer.print(&chunk)?;
+                    streamed.push_str(&chunk);
+                }
+                LogEvent::State(StreamState::Reconnecting) => eprintln!("(log stream interrupted, reconnecting)"),
+                LogEvent::State(_) => {}
+            },
+            _ = poll.tick() => {
+                let project = match client.get_project(project_id).await {
+                    Ok(project) => project,
+                    // A single failed poll shouldn't abort the wait
+                    Err(e) if e.is_transient() => continue,
+                    Err(e) => return Err(e.into()),
+                };
+                let deployment = project
+                    .deployments
+                    .unwrap_or_default()
+                    .into_iter()
+                    .find(|d| d.id == deployment_id);
+
+                if let Some(dep) = deployment
+                    && matches!(dep.status.as_str(), "live" | "failed" | "stopped")
+                {
+                    break Some(dep);
+                }
+            }
+            _ = sleep_until(deadline) => break None,
+        }
+    };
+
+    stream_task.abort();
+    while let Ok(event) = rx.try_recv() {
+        if let LogEvent::Chunk(chunk) = event {
+            printer.print(&chunk)?;
+            streamed.push_str(&chunk);
+        }
+    }
+
+    Ok(Followed { deployment, streamed })
+}
+
+async fn sleep_until(deadline: Option<tokio::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Splits the final stored log into the parts before and after the output
+/// that was already received live. If nothing was streamed, the whole log
+/// is treated as unseen trailing output. `None` if the streamed output
+/// can't be found in the stored log.
+fn split_around<'a>(stored: &'a str, streamed: &str) -> Option<(&'a str, &'a str)> {
+    if streamed.is_empty() {
+        return Some(("", stored));
+    }
+
+    let head = prefix_at_char_boundary(streamed, ANCHOR_LEN);
+    let tail = suffix_at_char_boundary(streamed, ANCHOR_LEN);
+
+    let before = &stored[..stored.find(head)?];
+    let after = &stored[stored.rfind(tail)? + tail.len()..];
+
+    Some((before, after))
+}
+
+fn prefix_at_char_boundary(s: &str, max: usize) -> &str {
+    let mut end = s.len().min(max);
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+fn suffix_at_char_boundary(s: &str, max: usize) -> &str {
+    let mut start = s.len().saturating_sub(max);
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+    &s[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_streamed_is_all_trailing() {
+        assert_eq!(split_around("Starting build...\nlive\n", ""), Some(("", "Starting build...\nlive\n")));
+    }
+
+    #[test]
+    fn splits_around_streamed_part() {
+        let stored = "Starting build...\nstep 1\nstep 2\n\nBuild Error: exit 1";
+        assert_eq!(split_around(stored, "step 1\nstep 2\n"), Some(("Starting build...\n", "\nBuild Error: exit 1")));
+        assert_eq!(split_around(stored, stored), Some(("", "")));
+    }
+
+    #[test]
+    fn unknown_streamed_part_falls_back_to_full_log() {
+        assert_eq!(split_around("stored only\n", "something else"), None);
+
+        // Chunks dropped by the server leave one end of the stream unmatched
+        let long = "x".repeat(ANCHOR_LEN);
+        let stored = format!("{}\n{}\nBuild Error: exit 1", long, "y".repeat(ANCHOR_LEN));
+        let streamed = format!("{}\nlost chunk", long);
+        assert_eq!(split_around(&stored, &streamed), None);
+    }
+
+    #[test]
+    fn long_streams_anchor_on_their_ends() {
+        // Multibyte characters straddle the anchor cut on both ends
+        let streamed = format!("x{}middle{}y", "é".repeat(150), "ü".repeat(150));
+        let stored = format!("before\n{}after", streamed);
+        assert_eq!(split_around(&stored, &streamed), Some(("before\n", "after")));
+    }
+
+    #[test]
+    fn repeated_output_ends_at_last_copy() {
+        let stored = "head\nok\nok\ndone";
+        assert_eq!(split_around(stored, "ok\n"), Some(("head\n", "done")));
+    }
+}
@@ -0,0 +1,51 @@
+pub mod activity;
+pub mod context;
+pub mod db;
+pub mod deploy;
+pub mod env;
+pub mod logs;
+pub mod projects;
+
+use crate::api::ApiClient;
+use crate::cli::Command;
+use crate::models::{Database, Project};
+use crate::output::OutputFormat;
+use anyhow::Result;
+
+/// Exit code when a waited-for deployment ends up failed or stopped.
+pub const EXIT_BUILD_FAILED: i32 = 2;
+/// Exit code when waiting for a deployment timed out.
+pub const EXIT_TIMEOUT: i32 = 3;
+
+pub async fn run(command: Command, client: &ApiClient, output: OutputFormat) -> Result<()> {
+    match command {
+        Command::Projects(cmd) => projects::run(cmd, client, output).await,
+        Command::Deploy(args) => deploy::deploy(args, client).await,
+        Command::Stop(args) => deploy::stop(args, client).await,
+        Command::Db(cmd) => db::run(cmd, client, output).await,
+        Command::Activity => activity::run(client, output).await,
+        Command::Logs(args) => logs::run(args, client).await,
+        Command::Env(cmd) => env::run(cmd, client).await,
+        Command::Context(cmd) => context::run(cmd, output).await,
+    }
+}
+
+/// Looks up a project by ID or by name.
+pub async fn resolve_project(client: &ApiClient, ident: &str) -> Result<Project> {
+    let projects = client.list_projects().await?;
+
+    projects
+        .into_iter()
+        .find(|p| p.id == ident || p.name == ident)
+        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", ident))
+}
+
+/// Looks up a database by ID or by name.
+pub async fn resolve_database(client: &ApiClient, ident: &str) -> Result<Database> {
+    let databases = client.list_databases().await?;
+
+    databases
+        .into_iter()
+        .find(|d| d.id.to_string() == ident || d.name == ident)
+        .ok_or_else(|| anyhow::anyhow!("Database '{}' not found", ident))
+}
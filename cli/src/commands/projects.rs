@@ -0,0 +1,67 @@
+use crate::api::ApiClient;
+use crate::cli::{CreateProjectArgs, ProjectsCommand};
+use crate::commands::resolve_project;
+use crate::models::CreateProjectRequest;
+use crate::output::{print_item, print_list, OutputFormat};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+pub async fn run(command: ProjectsCommand, client: &ApiClient, output: OutputFormat) -> Result<()> {
+    match command {
+        ProjectsCommand::List => list(client, output).await,
+        ProjectsCommand::Get { project } => get(client, &project, output).await,
+        ProjectsCommand::Create(args) => create(client, args, output).await,
+    }
+}
+
+async fn list(client: &ApiClient, output: OutputFormat) -> Result<()> {
+    let projects = client.list_projects().await?;
+    print_list(output, &projects)
+}
+
+async fn get(client: &ApiClient, ident: &str, output: OutputFormat) -> Result<()> {
+    let project = resolve_project(client, ident).await?;
+    let project = client.get_project(&project.id).await?;
+
+    print_item(output, &project)?;
+
+    // Structured formats already include the deployments in the project record
+    if output == OutputFormat::Table
+        && let Some(deployments) = project.deployments.as_ref().filter(|d| !d.is_empty())
+    {
+        println!();
+        print_list(output, deployments)?;
+    }
+
+    Ok(())
+}
+
+async fn create(client: &ApiClient, args: CreateProjectArgs, output: OutputFormat) -> Result<()> {
+    let env_vars = if args.env_vars.is_empty() {
+        None
+    } else {
+        let mut map = HashMap::new();
+        for pair in &args.env_vars {
+            let (key, value) = pair
+                .split_once('=')
+                .with_context(|| format!("Invalid environment variable '{}', expected KEY=VALUE", pair))?;
+            map.insert(key.to_string(), value.to_string());
+        }
+        Some(map)
+    };
+
+    let request = CreateProjectRequest {
+        name: args.name,
+        repo: args.repo,
+        port: Some(args.port),
+        git_token: args.git_token,
+        env_vars,
+        build_command: args.build_command,
+        start_command: args.start_command,
+        install_command: args.install_command,
+        runtime: args.runtime,
+    };
+
+    let project = client.create_project(request).await?;
+    print_item(output, &project)
+}
@@ -1,23 +1,160 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Config {
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Connection details for one Clickploy server.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Profile {
     pub server_url: String,
     pub api_key: String,
 }
 
-impl Config {
-    pub fn new(server_url: String, api_key: String) -> Self {
+/// Timeouts and retries applied to every API request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RequestPolicy {
+    /// Seconds to wait for a response before giving up on an attempt.
+    pub timeout_secs: u64,
+    /// Extra attempts for GET and PUT requests that fail with a network
+    /// error, a timeout or a 5xx. Other methods are never retried.
+    pub retries: u32,
+    /// Delay before the first retry; doubles on each further attempt.
+    pub backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
         Self {
-            server_url,
-            api_key,
+            timeout_secs: 30,
+            retries: 3,
+            backoff_ms: 250,
+            max_backoff_ms: 5000,
         }
     }
 }
 
+impl RequestPolicy {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    /// Delay before retry number `attempt` (starting at 0): exponential,
+    /// capped, with full jitter so clients don't retry in lockstep.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .backoff_ms
+            .saturating_mul(1u64 << attempt.min(16))
+            .min(self.max_backoff_ms);
+        Duration::from_millis(fastrand::u64(0..=ceiling))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(from = "RawConfig")]
+pub struct Config {
+    pub current_context: String,
+    pub profiles: BTreeMap<String, Profile>,
+    pub request: RequestPolicy,
+}
+
+/// On-disk shape, which also accepts the single-server format written by
+/// older versions (`server_url`/`api_key` at the top level).
+#[derive(Deserialize)]
+struct RawConfig {
+    current_context: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+    server_url: Option<String>,
+    api_key: Option<String>,
+    #[serde(default)]
+    request: RequestPolicy,
+}
+
+impl From<RawConfig> for Config {
+    fn from(raw: RawConfig) -> Self {
+        let mut profiles = raw.profiles;
+
+        if let (Some(server_url), Some(api_key)) = (raw.server_url, raw.api_key) {
+            profiles
+                .entry(DEFAULT_PROFILE.to_string())
+                .or_insert(Profile { server_url, api_key });
+        }
+
+        let current_context = raw
+            .current_context
+            .filter(|name| profiles.contains_key(name))
+            .or_else(|| profiles.keys().next().cloned())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+        Self {
+            current_context,
+            profiles,
+            request: raw.request,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            current_context: DEFAULT_PROFILE.to_string(),
+            profiles: BTreeMap::new(),
+            request: RequestPolicy::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Returns the named profile, or the current one when `name` is `None`.
+    pub fn profile(&self, name: Option<&str>) -> Result<(&str, &Profile)> {
+        let name = name.unwrap_or(&self.current_context);
+
+        self.profiles
+            .get_key_value(name)
+            .map(|(name, profile)| (name.as_str(), profile))
+            .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", name))
+    }
+
+    pub fn use_profile(&mut self, name: &str) -> Result<()> {
+        if !self.profiles.contains_key(name) {
+            anyhow::bail!("Profile '{}' not found", name);
+        }
+        self.current_context = name.to_string();
+        Ok(())
+    }
+
+    /// Adds or replaces a profile. The first profile becomes the current one.
+    pub fn add_profile(&mut self, name: &str, profile: Profile) {
+        self.profiles.insert(name.to_string(), profile);
+        if !self.profiles.contains_key(&self.current_context) {
+            self.current_context = name.to_string();
+        }
+    }
+
+    /// Removes a profile, switching to another one if it was current.
+    pub fn remove_profile(&mut self, name: &str) -> Result<()> {
+        if self.profiles.remove(name).is_none() {
+            anyhow::bail!("Profile '{}' not found", name);
+        }
+        if self.current_context == name {
+            self.current_context = self
+                .profiles
+                .keys()
+                .next()
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        }
+        Ok(())
+    }
+}
+
 pub fn get_config_path() -> Result<PathBuf> {
     let config_dir = dirs::config_dir()
         .context("Failed to get config directory")?
@@ -31,7 +168,7 @@ pub fn get_config_path() -> Result<PathBuf> {
 
 pub fn load_config() -> Result<Config> {
     let config_path = get_config_path()?;
-    
+
     if !config_path.exists() {
         anyhow::bail!("Config file not found. Please run setup first.");
     }
@@ -47,16 +184,32 @@ pub fn load_config() -> Result<Config> {
 
 pub fn save_config(config: &Config) -> Result<()> {
     let config_path = get_config_path()?;
-    
+
     let contents = toml::to_string_pretty(config)
         .context("Failed to serialize config")?;
 
-    fs::write(&config_path, contents)
+    write_private(&config_path, contents.as_bytes())
         .context("Failed to write config file")?;
 
     Ok(())
 }
 
+/// Writes a file only the current user can read, since it holds API keys
+/// or data fetched with them. Files left readable by an older version are
+/// tightened too.
+pub fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+
+    file.write_all(contents)
+}
+
 pub fn config_exists() -> bool {
     get_config_path()
         .map(|path| path.exists())
@@ -65,7 +218,7 @@ pub fn config_exists() -> bool {
 
 pub fn delete_config() -> Result<()> {
     let config_path = get_config_path()?;
-    
+
     if config_path.exists() {
         fs::remove_file(&config_path)
             .context("Failed to delete config file")?;
@@ -0,0 +1,202 @@
+use crate::config::write_private;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub type EnvMap = BTreeMap<String, String>;
+
+pub fn read_file(path: &Path) -> Result<EnvMap> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Writes `vars` readable only by the current user, since they're secrets.
+pub fn write_file(path: &Path, vars: &EnvMap) -> Result<()> {
+    write_private(path, serialize(vars).as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Parses dotenv syntax: `KEY=value` lines with optional `export ` prefix,
+/// `#` comments, and single- or double-quoted values.
+pub fn parse(contents: &str) -> Result<EnvMap> {
+    let mut vars = EnvMap::new();
+
+    for (index, raw) in contents.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("line {}: expected KEY=VALUE", index + 1))?;
+
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            anyhow::bail!("line {}: invalid variable name '{}'", index + 1, key);
+        }
+
+        let value = parse_value(value.trim())
+            .with_context(|| format!("line {}: unterminated quoted value", index + 1))?;
+        vars.insert(key.to_string(), value);
+    }
+
+    Ok(vars)
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Some(out),
+                '\\' => match chars.next()? {
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    other => out.push(other),
+                },
+                c => out.push(c),
+            }
+        }
+        None
+    } else if let Some(rest) = value.strip_prefix('\'') {
+        rest.find('\'').map(|end| rest[..end].to_string())
+    } else {
+        // Unquoted values end at an inline comment
+        let end = value.find(" #").unwrap_or(value.len());
+        Some(value[..end].trim_end().to_string())
+    }
+}
+
+pub fn serialize(vars: &EnvMap) -> String {
+    let mut out = String::new();
+    for (key, value) in vars {
+        out.push_str(key);
+        out.push('=');
+        out.push_str(&quote(value));
+        out.push('\n');
+    }
+    out
+}
+
+fn quote(value: &str) -> String {
+    let plain = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "_-.,:/@+=%".contains(c));
+    if plain {
+        return value.to_string();
+    }
+
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Differences between two sets of variables, going from `from` to `to`.
+#[derive(Debug, Default)]
+pub struct EnvDiff {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl EnvDiff {
+    pub fn between(from: &EnvMap, to: &EnvMap) -> Self {
+        let mut diff = Self::default();
+        for (key, value) in to {
+            match from.get(key) {
+                None => diff.added.push(key.clone()),
+                Some(old) if old != value => diff.changed.push(key.clone()),
+                Some(_) => {}
+            }
+        }
+        for key in from.keys() {
+            if !to.contains_key(key) {
+                diff.removed.push(key.clone());
+            }
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(line: &str) -> String {
+        parse(line).unwrap().remove("KEY").unwrap()
+    }
+
+    #[test]
+    fn reads_plain_and_exported_lines() {
+        let vars = parse("# comment\n\nexport A=1\nB = two words\nC=x # note\nD=\n").unwrap();
+        assert_eq!(vars["A"], "1");
+        assert_eq!(vars["B"], "two words");
+        assert_eq!(vars["C"], "x");
+        assert_eq!(vars["D"], "");
+    }
+
+    #[test]
+    fn double_quotes_take_escapes() {
+        assert_eq!(value(r#"KEY="a\nb\tc\r""#), "a\nb\tc\r");
+        assert_eq!(value(r#"KEY="say \"hi\" \\ bye""#), r#"say "hi" \ bye"#);
+        assert_eq!(value(r#"KEY="keeps # and = inside""#), "keeps # and = inside");
+    }
+
+    #[test]
+    fn single_quotes_are_literal() {
+        assert_eq!(value(r"KEY='a\nb # c'"), r"a\nb # c");
+    }
+
+    #[test]
+    fn rejects_bad_lines() {
+        assert!(parse("KEY=\"open").is_err());
+        assert!(parse("KEY='open").is_err());
+        assert!(parse("KEY=\"ends in escape\\").is_err());
+        assert!(parse("no equals sign").is_err());
+        assert!(parse("BAD-NAME=1").is_err());
+    }
+
+    #[test]
+    fn serialize_round_trips() {
+        let mut vars = EnvMap::new();
+        vars.insert("PLAIN".into(), "postgres://u@h:5432/db".into());
+        vars.insert("SPACED".into(), "two words # not a comment".into());
+        vars.insert("TRICKY".into(), "quote \" slash \\ line\nnext\ttab".into());
+        vars.insert("EMPTY".into(), String::new());
+
+        let text = serialize(&vars);
+        assert!(text.contains("PLAIN=postgres://u@h:5432/db\n"));
+        assert_eq!(parse(&text).unwrap(), vars);
+    }
+
+    #[test]
+    fn diff_sorts_keys_by_change() {
+        let from = parse("A=1\nB=2\nC=3").unwrap();
+        let to = parse("A=1\nB=20\nD=4").unwrap();
+        let diff = EnvDiff::between(&from, &to);
+        assert_eq!(diff.added, ["D"]);
+        assert_eq!(diff.changed, ["B"]);
+        assert_eq!(diff.removed, ["C"]);
+        assert!(EnvDiff::between(&to, &to).is_empty());
+    }
+}
@@ -1,52 +1,176 @@
 mod api;
+mod cache;
+mod cli;
+mod commands;
 mod config;
+mod dotenv;
 mod models;
+mod output;
+mod prompt;
 mod ui;
 
 use anyhow::Result;
-use api::ApiClient;
-use config::{config_exists, delete_config, load_config, save_config, Config};
+use api::{ApiClient, ApiError};
+use clap::Parser;
+use cli::{Cli, Command};
+use config::{config_exists, load_config, save_config, Config, Profile, DEFAULT_PROFILE};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
+use futures_util::StreamExt;
 use std::{io, time::Duration};
-use tokio::sync::mpsc;
+use ui::action::{Action, ActionResult, Tasks};
+use ui::component::{footer_height, render_footer, Ctx, Transition};
+use ui::mouse::{contains, is_left_click};
+use ui::navigator::Navigator;
+use ui::confirm::Answer;
+use ui::failure_analysis::FailureRules;
+use ui::help::HelpOverlay;
+use ui::keymap::{Key, KeyAction, Keymap, Scope};
+use ui::notifications::HistoryPanel;
+use ui::palette::{Choice, Palette};
 use ui::{App, Screen, SetupState};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Check if config exists
-    if !config_exists() {
-        // Run setup
-        run_setup().await?;
+    let cli = Cli::parse();
+
+    if let Some(command) = cli.command {
+        // Profiles can be managed before any server is configured
+        if let Command::Context(cmd) = command {
+            return commands::context::run(cmd, cli.output).await;
+        }
+
+        if !config_exists() {
+            anyhow::bail!("No configuration found. Run `clickploy` without arguments to set up the CLI.");
+        }
+
+        let config = load_config()?;
+        let (_, profile) = config.profile(cli.profile.as_deref())?;
+        let client = ApiClient::new(profile.server_url.clone(), profile.api_key.clone())
+            .with_policy(config.request.clone());
+        return commands::run(command, &client, cli.output).await;
     }
 
-    // Load config
-    let config = load_config()?;
+    let mut force_setup = false;
 
-    // Validate connection
-    let client = ApiClient::new(config.server_url.clone(), config.api_key.clone());
-    
-    match client.validate_connection().await {
-        Ok(user) => {
-            // Run main app
-            run_app(client, user).await?;
+    loop {
+        // Check if config exists
+        let profile_name = cli.profile.clone().unwrap_or_else(|| {
+            load_config()
+                .map(|c| c.current_context)
+                .unwrap_or_else(|_| DEFAULT_PROFILE.to_string())
+        });
+
+        if force_setup || !config_exists() || load_config()?.profiles.is_empty() {
+            // Run setup
+            run_setup(&profile_name).await?;
         }
-        Err(e) => {
-            eprintln!("Failed to connect to server: {}", e);
-            eprintln!("Please reconfigure by running the setup again.");
-            delete_config()?;
-            std::process::exit(1);
+
+        // Load config
+        let config = load_config()?;
+        let (profile_name, profile) = config.profile(Some(&profile_name))?;
+        let profile_name = profile_name.to_string();
+
+        // Validate connection
+        let client = ApiClient::new(profile.server_url.clone(), profile.api_key.clone())
+            .with_policy(config.request.clone());
+        let mut user = None;
+        let mut offline = None;
+
+        loop {
+            match client.validate_connection().await {
+                Ok(validated) => {
+                    user = Some(validated);
+                    break;
+                }
+                Err(e) => {
+                    // Never drop the saved credentials here: most failures are transient
+                    let snapshot = cache::load(&profile_name);
+                    match ask_after_failed_connection(&profile_name, &profile.server_url, &e, snapshot.is_some())? {
+                        StartupChoice::Retry => {}
+                        StartupChoice::Offline => {
+                            offline = snapshot;
+                            break;
+                        }
+                        StartupChoice::Reconfigure => break,
+                        StartupChoice::Quit => std::process::exit(1),
+                    }
+                }
+            }
         }
+
+        if user.is_none() && offline.is_none() {
+            force_setup = true;
+            continue;
+        }
+
+        // Run main app
+        let reconfigure = run_app(client, user, offline, config, profile_name).await?;
+        if !reconfigure {
+            break;
+        }
+        force_setup = true;
     }
 
     Ok(())
 }
 
-async fn run_setup() -> Result<()> {
+enum StartupChoice {
+    Retry,
+    Offline,
+    Reconfigure,
+    Quit,
+}
+
+/// Explains why the server could not be used and lets the user decide what to
+/// do. Auth failures suggest reconfiguring; anything else suggests retrying.
+fn ask_after_failed_connection(
+    profile_name: &str,
+    server_url: &str,
+    error: &ApiError,
+    has_cache: bool,
+) -> Result<StartupChoice> {
+    eprintln!("Could not sign in to {} (profile '{}'): {}", server_url, profile_name, error);
+
+    // Transient failures are worth retrying; anything else (a rejected key,
+    // or a 404 from a wrong server URL) needs the profile fixed
+    let mut choices = Vec::new();
+    if error.is_transient() {
+        eprintln!("Your saved credentials were kept. The server may be down or unreachable from this network.");
+        choices.push(('r', "retry"));
+        if has_cache {
+            choices.push(('o', "offline (read-only)"));
+        }
+        choices.push(('c', "reconfigure"));
+    } else {
+        if error.is_auth() {
+            eprintln!("The server rejected the saved API key. It may have been revoked or regenerated.");
+        } else {
+            eprintln!("The server URL may be wrong. Your saved credentials were kept.");
+        }
+        choices.push(('c', "reconfigure"));
+        choices.push(('r', "retry"));
+        if has_cache {
+            choices.push(('o', "offline (read-only)"));
+        }
+    }
+    choices.push(('q', "quit"));
+
+    let choice = match prompt::choose("What now?", &choices)? {
+        'r' => StartupChoice::Retry,
+        'o' => StartupChoice::Offline,
+        'c' => StartupChoice::Reconfigure,
+        _ => StartupChoice::Quit,
+    };
+
+    Ok(choice)
+}
+
+async fn run_setup(profile_name: &str) -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -58,12 +182,12 @@ async fn run_setup() -> Result<()> {
 
     loop {
         terminal.draw(|f| {
-            ui::setup::render(f, f.area(), &setup_state);
+            ui::setup::render(f, f.area(), &mut setup_state);
         })?;
 
         if event::poll(Duration::from_millis(100))? {
-                        if let Event::Key(key) = event::read()? {
-                match key.code {
+            match event::read()? {
+                Event::Key(key) => match key.code {
                     KeyCode::Esc => {
                         should_quit = true;
                         break;
@@ -109,8 +233,10 @@ async fn run_setup() -> Result<()> {
                         
                         match test_client.validate_connection().await {
                             Ok(_) => {
-                                // Save config
-                                let config = Config::new(server_url, api_key);
+                                // Save config, keeping any other profiles
+                                let mut config = load_config().unwrap_or_default();
+                                config.add_profile(profile_name, Profile { server_url, api_key });
+                                config.current_context = profile_name.to_string();
                                 if let Err(e) = save_config(&config) {
                                     setup_state.error = Some(format!("Failed to save config: {}", e));
                                     continue;
@@ -137,28 +263,13 @@ async fn run_setup() -> Result<()> {
                         }
                     }
                     _ => {}
-                }
-            } else if let Event::Mouse(mouse) = event::read()? {
-                if mouse.kind == event::MouseEventKind::Down(crossterm::event::MouseButton::Left) {
-                    // Simple hit testing based on known layout in setup.rs
-                    // URL input is at chunks[1]
-                    // API key input is at chunks[2]
-                    // We need to know where these chunks are. 
-                    // Since we can't easily share layout state, we'll approximate or toggle.
-                    // Actually, a simpler way for now is just to support field switching via click 
-                    // if we know the Y coordinates.
-                    // Layout:
-                    // Title: 7 lines
-                    // URL: 3 lines (y: 7-9)
-                    // API: 3 lines (y: 10-12)
-                    
-                    let y = mouse.row;
-                    if y >= 7 && y < 10 {
-                        setup_state.focused_field = 0;
-                    } else if y >= 10 && y < 13 {
-                        setup_state.focused_field = 1;
+                },
+                Event::Mouse(mouse) if is_left_click(&mouse) => {
+                    if let Some(field) = setup_state.field_at(&mouse) {
+                        setup_state.focused_field = field;
                     }
                 }
+                _ => {}
             }
         }
     }
@@ -178,7 +289,14 @@ async fn run_setup() -> Result<()> {
     Ok(())
 }
 
-async fn run_app(client: ApiClient, user: models::User) -> Result<()> {
+/// Runs the TUI. Returns `true` when the user asked to reconfigure the active profile.
+async fn run_app(
+    client: ApiClient,
+    user: Option<models::User>,
+    offline: Option<cache::Snapshot>,
+    config: Config,
+    profile_name: String,
+) -> Result<bool> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -186,14 +304,34 @@ async fn run_app(client: ApiClient, user: models::User) -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new();
+    let mut tasks = Tasks::new(client);
     app.screen = Screen::Projects;
-    app.user = Some(user.clone());
-    app.message = format!("Welcome, {}!", user.name);
+    app.config = config;
+    app.active_profile = profile_name;
+    let (keys, problems) = Keymap::load();
+    app.keys = keys;
+    for problem in problems {
+        app.notifications.error(problem);
+    }
+    let (failure_rules, problems) = FailureRules::load();
+    app.failure_rules = failure_rules;
+    for problem in problems {
+        app.notifications.error(problem);
+    }
+
+    if let Some(snapshot) = offline {
+        app.enter_offline_mode(snapshot);
+    } else {
+        if let Some(user) = user {
+            app.notifications.info(format!("Welcome, {}!", user.name));
+            app.user = Some(user);
+        }
 
-    // Initial data fetch
-    app.fetch_projects(&client).await?;
+        // Initial data fetch
+        tasks.dispatch(&mut app, Action::FetchProjects);
+    }
 
-    let res = run_app_loop(&mut terminal, &mut app, &client).await;
+    let res = run_app_loop(&mut terminal, &mut app, &mut tasks).await;
 
     disable_raw_mode()?;
     execute!(
@@ -207,86 +345,26 @@ async fn run_app(client: ApiClient, user: models::User) -> Result<()> {
         println!("{:?}", err);
     }
 
-    Ok(())
-}
-
-async fn stream_logs(server_url: String, deployment_id: String, tx: mpsc::UnboundedSender<String>) -> Result<()> {
-    use tokio_tungstenite::connect_async;
-    use futures_util::StreamExt;
-
-    let ws_url = server_url
-        .replace("http://", "ws://")
-        .replace("https://", "wss://");
-    let url = format!("{}/api/deployments/{}/logs/stream", ws_url, deployment_id);
-
-    let (ws_stream, _) = connect_async(&url).await?;
-    let (_, mut read) = ws_stream.split();
-
-    while let Some(msg) = read.next().await {
-        match msg {
-            Ok(msg) => {
-                if let Ok(text) = msg.to_text() {
-                    if tx.send(text.to_string()).is_err() {
-                        break;
-                    }
-                }
-            }
-            Err(_) => break,
-        }
-    }
-
-    Ok(())
+    Ok(app.reconfigure)
 }
 
 async fn run_app_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
-    client: &ApiClient,
+    tasks: &mut Tasks,
 ) -> Result<()> {
-    let (log_tx, mut log_rx) = mpsc::unbounded_channel::<String>();
-    let mut ws_task: Option<tokio::task::JoinHandle<()>> = None;
-    let mut current_deployment_id: Option<String> = None;
+    let mut events = EventStream::new();
+    let mut tick = tokio::time::interval(Duration::from_millis(100));
+    let mut nav = Navigator::new(&mut Ctx::new(app, tasks));
+    let mut palette: Option<Palette> = None;
+    let mut history: Option<HistoryPanel> = None;
+    let mut help: Option<HelpOverlay> = None;
+    // Where each clickable footer hint was drawn, with the key it stands for
+    let mut footer: Vec<(Rect, KeyEvent)> = Vec::new();
 
     loop {
-        // Check if we need to start/stop WebSocket connection
-        if let Screen::DeploymentLogs(ref id) = app.screen {
-            if current_deployment_id.as_ref() != Some(id) {
-                // Stop old task
-                if let Some(task) = ws_task.take() {
-                    task.abort();
-                }
-                
-                // Start new WebSocket connection
-                let deployment_id = id.clone();
-                let server_url = load_config().ok().map(|c| c.server_url).unwrap_or_else(|| "http://localhost:8080".to_string());
-                let tx = log_tx.clone();
-                
-                ws_task = Some(tokio::spawn(async move {
-                    if let Err(e) = stream_logs(server_url, deployment_id, tx).await {
-                        eprintln!("WebSocket error: {}", e);
-                    }
-                }));
-                
-                current_deployment_id = Some(id.clone());
-                app.live_logs.clear();
-            }
-        } else if ws_task.is_some() {
-            // Stop WebSocket if we're not on logs screen
-            if let Some(task) = ws_task.take() {
-                task.abort();
-            }
-            current_deployment_id = None;
-            app.live_logs.clear();
-        }
-
-        // Check for new log messages
-        while let Ok(log_chunk) = log_rx.try_recv() {
-            app.live_logs.push_str(&log_chunk);
-            // Auto-scroll to bottom if at bottom
-            if app.log_scroll >= u16::MAX - 10 {
-                app.log_scroll = u16::MAX;
-            }
-        }
+        nav.current().tick(&mut Ctx::new(app, tasks));
+        let hints = nav.current().keymap(&app.keys);
 
         terminal.draw(|f| {
             // Split layout for status bar
@@ -301,509 +379,204 @@ async fn run_app_loop(
             let content_area = chunks[0];
             let status_area = chunks[1];
 
-            match &app.screen {
-                Screen::Setup => {
-                    // Should not reach here
-                }
-                Screen::Projects => {
-                    ui::projects::render(f, content_area, app);
-                }
-                Screen::CreateProject => {
-                    ui::create_project::render(f, content_area, &app.create_project_state);
-                }
-                Screen::DeploymentLogs(id) => {
-                    ui::deployment_logs::render(f, content_area, app, id);
-                }
-                Screen::ProjectDetail(_id) => {
-                    ui::project_detail::render(f, content_area, app);
-                }
-                Screen::ProjectSettings(_id) => {
-                    ui::project_settings::render(f, content_area, app);
-                }
-                Screen::Deployments => {
-                    ui::deployments::render(f, content_area, app);
-                }
-                Screen::Activity => {
-                    ui::activity::render(f, content_area, app);
-                }
-                Screen::Network => {
-                    ui::network::render(f, content_area, app);
-                }
-                Screen::Storage => {
-                    ui::storage::render(f, content_area, app);
-                }
-                Screen::CreateDatabase => {
-                    ui::storage::render_create_database(f, content_area, app);
-                }
-                Screen::Docs => {
-                    ui::docs::render(f, content_area, app);
-                }
-                Screen::Settings => {
-                    if let Ok(config) = load_config() {
-                        ui::settings::render(f, content_area, &config);
-                    }
-                }
+            let body = ratatui::layout::Layout::default()
+                .direction(ratatui::layout::Direction::Vertical)
+                .constraints([
+                    ratatui::layout::Constraint::Min(0),
+                    ratatui::layout::Constraint::Length(footer_height(&hints, content_area.width)),
+                ])
+                .split(content_area);
+            nav.current().render(f, body[0], app);
+            let areas = render_footer(f, body[1], &hints);
+            footer = areas
+                .into_iter()
+                .zip(&hints)
+                .filter_map(|(area, hint)| Some((area, hint.press?)))
+                .collect();
+            if let Some(palette) = &palette {
+                palette.render(f, content_area);
             }
-
-            // Render Status Bar
-            let status_text = format!(
-                " Clickploy CLI | User: {} | Screen: {:?} | q: Quit", 
-                app.user.as_ref().map(|u| u.name.as_str()).unwrap_or("Unknown"),
-                app.screen
-            );
+            if let Some(history) = &history {
+                history.render(f, content_area, &app.notifications);
+            }
+            if let Some(help) = &mut help {
+                help.render(f, content_area, &app.keys);
+            }
+            if let Some(confirm) = &app.confirm {
+                confirm.dialog.render(f, content_area);
+            }
+            app.notifications.render_toasts(f, content_area);
+
+            // Render Status Bar, with whatever is still loading in front
+            let key = |action| app.keys.key(Scope::Global, action).unwrap_or_default();
+            let status_text = match app.pending.last() {
+                Some(pending) => format!(
+                    " {} {} ({:.1}s){} | {}: Cancel",
+                    pending.spinner(),
+                    pending.label,
+                    pending.started.elapsed().as_secs_f32(),
+                    if app.pending.len() > 1 { format!(" +{} more", app.pending.len() - 1) } else { String::new() },
+                    key(KeyAction::CancelRequest),
+                ),
+                None => format!(
+                    " {} | User: {} | Profile: {}{} | {}: Notifications{} | {}: Commands | {}/{}: Back/Forward | {}: Help | {}: Quit",
+                    nav.breadcrumbs(app),
+                    app.user.as_ref().map(|u| u.name.as_str()).unwrap_or("Unknown"),
+                    app.active_profile,
+                    if app.offline { " (OFFLINE, read-only)" } else { "" },
+                    key(KeyAction::Notifications),
+                    match app.notifications.unread() {
+                        0 => String::new(),
+                        unread => format!(" ({})", unread),
+                    },
+                    key(KeyAction::Palette),
+                    key(KeyAction::HistoryBack),
+                    key(KeyAction::HistoryForward),
+                    key(KeyAction::Help),
+                    key(KeyAction::Quit),
+                ),
+            };
             let status_bar = ratatui::widgets::Paragraph::new(status_text)
                 .style(ratatui::style::Style::default().fg(ratatui::style::Color::Black).bg(ratatui::style::Color::Cyan));
             f.render_widget(status_bar, status_area);
 
         })?;
 
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                // Global keys
-                match key.code {
-                    KeyCode::Char('q') => {
-                        app.should_quit = true;
-                        return Ok(());
+        // Wait for input, a finished action or the next frame
+        let event = tokio::select! {
+            Some(event) = events.next() => event?,
+            (id, result) = tasks.next() => {
+                if tasks.finish(app, id) {
+                    if let ActionResult::ProfileSwitched { client, result: Ok(_), .. } = &result {
+                        tasks.switch_client(app, client.clone());
+                        nav.reset_history();
                     }
-                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        app.should_quit = true;
-                        return Ok(());
+                    let mut ctx = Ctx::new(app, tasks);
+                    let transition = nav.current().on_result(&result, ctx.app);
+                    nav.go(transition, &mut ctx);
+                    for action in ctx.app.apply(result) {
+                        ctx.dispatch(action);
+                    }
+                    if let Some(palette) = &mut palette {
+                        palette.refresh(app);
                     }
-                    _ => {}
                 }
+                continue;
+            }
+            _ = tick.tick() => continue,
+        };
+
+        // Dialogs and overlays only take keys
+        let modal = app.confirm.is_some() || help.is_some() || history.is_some() || palette.is_some();
+        let event = match event {
+            Event::Mouse(_) if modal => continue,
+            // A click on a footer hint presses its key
+            Event::Mouse(mouse) if is_left_click(&mouse) => footer
+                .iter()
+                .find(|(area, _)| contains(*area, &mouse))
+                .map_or(event, |(_, press)| Event::Key(*press)),
+            event => event,
+        };
+
+        // Global keys
+        if let Event::Key(key) = &event {
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            if ctrl && key.code == KeyCode::Char('c') {
+                app.should_quit = true;
+                return Ok(());
+            }
 
-                // Screen-specific keys
-                match app.screen.clone() {
-                    Screen::Projects => {
-                        match key.code {
-                            KeyCode::Down | KeyCode::Char('j') => app.next(),
-                            KeyCode::Up | KeyCode::Char('k') => app.previous(),
-                            KeyCode::Enter => {
-                                app.select_project();
-                                if let Screen::ProjectDetail(id) = &app.screen.clone() {
-                                    app.fetch_project_detail(client, id).await?;
-                                }
-                            }
-                            KeyCode::Char('n') => {
-                                app.screen = Screen::CreateProject;
-                                app.create_project_state = ui::CreateProjectState::new();
-                            }
-                            KeyCode::Char('r') => {
-                                app.fetch_projects(client).await?;
-                            }
-                            KeyCode::Char('a') => {
-                                app.screen = Screen::Activity;
-                                app.selected_index = 0;
-                                app.fetch_activity(client).await?;
-                            }
-                            KeyCode::Char('d') => {
-                                app.screen = Screen::Deployments;
-                                app.selected_index = 0;
-                                app.fetch_activity(client).await?;
-                            }
-                            KeyCode::Char('w') => {
-                                app.screen = Screen::Network;
-                            }
-                            KeyCode::Char('t') => {
-                                app.screen = Screen::Storage;
-                                app.selected_index = 0;
-                                app.fetch_storage_data(client).await?;
-                            }
-                            KeyCode::Char('h') => {
-                                app.screen = Screen::Docs;
-                            }
-                            KeyCode::Char('s') => {
-                                app.screen = Screen::Settings;
-                            }
-                            _ => {}
+            // A confirmation dialog takes all keys until it is answered
+            if let Some(confirm) = &mut app.confirm {
+                match confirm.dialog.handle_key(key) {
+                    Answer::Pending => {}
+                    Answer::No => app.confirm = None,
+                    Answer::Yes => {
+                        if let Some(confirm) = app.confirm.take() {
+                            tasks.dispatch(app, confirm.action);
                         }
                     }
-                    Screen::CreateProject => {
-                        match key.code {
-                            KeyCode::Esc => {
-                                app.screen = Screen::Projects;
-                            }
-                            KeyCode::Tab => {
-                                app.create_project_state.next_field();
-                            }
-                            KeyCode::BackTab => {
-                                app.create_project_state.previous_field();
-                            }
-                            KeyCode::Enter => {
-                                // Create project
-                                let name = app.create_project_state.name.value().trim().to_string();
-                                let repo = app.create_project_state.repo_url.value().trim().to_string();
-                                
-                                if name.is_empty() || repo.is_empty() {
-                                    app.create_project_state.error = Some("Name and Repo URL are required".to_string());
-                                    continue;
-                                }
+                }
+                continue;
+            }
 
-                                let build_cmd = app.create_project_state.build_command.value().trim();
-                                let start_cmd = app.create_project_state.start_command.value().trim();
-                                let install_cmd = app.create_project_state.install_command.value().trim();
-
-                                let request = models::CreateProjectRequest {
-                                    name,
-                                    repo,
-                                    port: Some(3000), // Default port
-                                    git_token: None,
-                                    env_vars: None,
-                                    build_command: if build_cmd.is_empty() { None } else { Some(build_cmd.to_string()) },
-                                    start_command: if start_cmd.is_empty() { None } else { Some(start_cmd.to_string()) },
-                                    install_command: if install_cmd.is_empty() { None } else { Some(install_cmd.to_string()) },
-                                    runtime: None,
-                                };
-
-                                app.message = "Creating project...".to_string();
-                                match client.create_project(request).await {
-                                    Ok(_) => {
-                                        app.message = "Project created successfully".to_string();
-                                        app.screen = Screen::Projects;
-                                        app.fetch_projects(client).await?;
-                                    }
-                                    Err(e) => {
-                                        app.create_project_state.error = Some(format!("Failed to create project: {}", e));
-                                    }
-                                }
-                            }
-                            KeyCode::Char(c) => {
-                                match app.create_project_state.focused_field {
-                                    0 => app.create_project_state.name.handle(tui_input::InputRequest::InsertChar(c)),
-                                    1 => app.create_project_state.repo_url.handle(tui_input::InputRequest::InsertChar(c)),
-                                    2 => app.create_project_state.install_command.handle(tui_input::InputRequest::InsertChar(c)),
-                                    3 => app.create_project_state.build_command.handle(tui_input::InputRequest::InsertChar(c)),
-                                    4 => app.create_project_state.start_command.handle(tui_input::InputRequest::InsertChar(c)),
-                                    _ => None,
-                                };
-                            }
-                            KeyCode::Backspace => {
-                                match app.create_project_state.focused_field {
-                                    0 => app.create_project_state.name.handle(tui_input::InputRequest::DeletePrevChar),
-                                    1 => app.create_project_state.repo_url.handle(tui_input::InputRequest::DeletePrevChar),
-                                    2 => app.create_project_state.install_command.handle(tui_input::InputRequest::DeletePrevChar),
-                                    3 => app.create_project_state.build_command.handle(tui_input::InputRequest::DeletePrevChar),
-                                    4 => app.create_project_state.start_command.handle(tui_input::InputRequest::DeletePrevChar),
-                                    _ => None,
-                                };
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::ProjectDetail(id) => {
-                        match key.code {
-                            KeyCode::Backspace => {
-                                app.go_back();
-                            }
-                            KeyCode::Char('r') => {
-                                // Redeploy
-                                app.message = "Redeploying...".to_string();
-                                match client.redeploy_project(&id, None).await {
-                                    Ok(_) => {
-                                        app.message = "Redeploy started successfully".to_string();
-                                        app.error = None;
-                                        // Refresh project details
-                                        tokio::time::sleep(Duration::from_millis(500)).await;
-                                        app.fetch_project_detail(client, &id).await?;
-                                    }
-                                    Err(e) => {
-                                        app.error = Some(format!("Redeploy failed: {}", e));
-                                    }
-                                }
-                            }
-                            KeyCode::Char('s') => {
-                                // Stop project
-                                app.message = "Stopping project...".to_string();
-                                match client.stop_project(&id).await {
-                                    Ok(_) => {
-                                        app.message = "Project stopped successfully".to_string();
-                                        app.error = None;
-                                        // Refresh project details
-                                        tokio::time::sleep(Duration::from_millis(500)).await;
-                                        app.fetch_project_detail(client, &id).await?;
-                                    }
-                                    Err(e) => {
-                                        app.error = Some(format!("Stop failed: {}", e));
-                                    }
-                                }
-                            }
-                            KeyCode::Char('l') => {
-                                // View Logs for latest deployment
-                                if let Some(project) = &app.selected_project {
-                                    if let Some(deployments) = &project.deployments {
-                                        if let Some(latest) = deployments.first() {
-                                            app.screen = Screen::DeploymentLogs(latest.id.clone());
-                                        } else {
-                                            app.error = Some("No deployments found".to_string());
-                                        }
-                                    }
-                                }
-                            }
-                            KeyCode::Char('c') => {
-                                // View project settings
-                                app.screen = Screen::ProjectSettings(id.clone());
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::ProjectSettings(_id) => {
-                        match key.code {
-                            KeyCode::Backspace => {
-                                app.go_back();
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::Deployments => {
-                        match key.code {
-                            KeyCode::Backspace => {
-                                app.go_back();
-                            }
-                            KeyCode::Char('r') => {
-                                app.fetch_activity(client).await?;
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::Network => {
-                        match key.code {
-                            KeyCode::Backspace => {
-                                app.go_back();
-                            }
-                            KeyCode::Char('r') => {
-                                app.fetch_projects(client).await?;
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::Storage => {
-                        match key.code {
-                            KeyCode::Backspace | KeyCode::Esc => {
-                                if app.show_db_credentials {
-                                    app.show_db_credentials = false;
-                                    app.db_credentials = None;
-                                } else {
-                                    app.go_back();
-                                }
-                            }
-                            KeyCode::Up | KeyCode::Char('k') => {
-                                if !app.show_db_credentials {
-                                    app.previous();
-                                }
-                            }
-                            KeyCode::Down | KeyCode::Char('j') => {
-                                if !app.show_db_credentials {
-                                    app.next();
-                                }
-                            }
-                            KeyCode::Enter => {
-                                if !app.show_db_credentials && !app.databases.is_empty() {
-                                    app.select_database();
-                                    if let Some(db) = &app.selected_database {
-                                        if db.db_type == "mongodb" {
-                                            // Fetch credentials
-                                            match client.get_database_credentials(db.id).await {
-                                                Ok(creds) => {
-                                                    app.db_credentials = Some(creds);
-                                                }
-                                                Err(e) => {
-                                                    app.error = Some(format!("Failed to fetch credentials: {}", e));
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            KeyCode::Char('n') => {
-                                if !app.show_db_credentials {
-                                    app.create_database_state.reset();
-                                    app.screen = Screen::CreateDatabase;
-                                }
-                            }
-                            KeyCode::Char('d') => {
-                                if !app.show_db_credentials && !app.databases.is_empty() && app.selected_index < app.databases.len() {
-                                    let db_id = app.databases[app.selected_index].id;
-                                    match client.delete_database(db_id).await {
-                                        Ok(_) => {
-                                            app.message = "Database deleted".to_string();
-                                            let _ = app.fetch_storage_data(client).await;
-                                        }
-                                        Err(e) => {
-                                            app.error = Some(format!("Failed to delete database: {}", e));
-                                        }
-                                    }
-                                }
-                            }
-                            KeyCode::Char('s') => {
-                                if !app.show_db_credentials && !app.databases.is_empty() && app.selected_index < app.databases.len() {
-                                    let db_id = app.databases[app.selected_index].id;
-                                    match client.stop_database(db_id).await {
-                                        Ok(_) => {
-                                            app.message = "Database stopped".to_string();
-                                            let _ = app.fetch_storage_data(client).await;
-                                        }
-                                        Err(e) => {
-                                            app.error = Some(format!("Failed to stop database: {}", e));
-                                        }
-                                    }
-                                }
-                            }
-                            KeyCode::Char('r') => {
-                                if !app.show_db_credentials && !app.databases.is_empty() && app.selected_index < app.databases.len() {
-                                    let db_id = app.databases[app.selected_index].id;
-                                    match client.restart_database(db_id).await {
-                                        Ok(_) => {
-                                            app.message = "Database restarted".to_string();
-                                            let _ = app.fetch_storage_data(client).await;
-                                        }
-                                        Err(e) => {
-                                            app.error = Some(format!("Failed to restart database: {}", e));
-                                        }
-                                    }
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::CreateDatabase => {
-                        match key.code {
-                            KeyCode::Esc => {
-                                app.create_database_state.reset();
-                                app.screen = Screen::Storage;
-                            }
-                            KeyCode::Tab => {
-                                app.create_database_state.focused_field = 
-                                    (app.create_database_state.focused_field + 1) % 2;
-                            }
-                            KeyCode::Char(c) => {
-                                if app.create_database_state.focused_field == 0 {
-                                    app.create_database_state.name.push(c);
-                                }
-                            }
-                            KeyCode::Backspace => {
-                                if app.create_database_state.focused_field == 0 {
-                                    app.create_database_state.name.pop();
-                                } else {
-                                    app.create_database_state.reset();
-                                    app.screen = Screen::Storage;
-                                }
-                            }
-                            KeyCode::Up => {
-                                if app.create_database_state.focused_field == 1 {
-                                    app.create_database_state.db_type = if app.create_database_state.db_type == "mongodb" {
-                                        "sqlite".to_string()
-                                    } else {
-                                        "mongodb".to_string()
-                                    };
-                                }
-                            }
-                            KeyCode::Down => {
-                                if app.create_database_state.focused_field == 1 {
-                                    app.create_database_state.db_type = if app.create_database_state.db_type == "sqlite" {
-                                        "mongodb".to_string()
-                                    } else {
-                                        "sqlite".to_string()
-                                    };
-                                }
-                            }
-                            KeyCode::Enter => {
-                                if !app.create_database_state.name.is_empty() {
-                                    let name = app.create_database_state.name.clone();
-                                    let db_type = app.create_database_state.db_type.clone();
-                                    
-                                    match client.create_database(name, db_type).await {
-                                        Ok(_) => {
-                                            app.message = "Database created successfully".to_string();
-                                            app.create_database_state.reset();
-                                            app.screen = Screen::Storage;
-                                            let _ = app.fetch_storage_data(client).await;
-                                        }
-                                        Err(e) => {
-                                            app.error = Some(format!("Failed to create database: {}", e));
-                                        }
-                                    }
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::Docs => {
-                        match key.code {
-                            KeyCode::Backspace => {
-                                app.go_back();
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::DeploymentLogs(_) => {
-                        match key.code {
-                            KeyCode::Backspace => {
-                                app.live_logs.clear();
-                                app.log_scroll = 0;
-                                app.go_back();
-                            }
-                            KeyCode::Down | KeyCode::Char('j') => {
-                                app.log_scroll = app.log_scroll.saturating_add(1);
-                            }
-                            KeyCode::Up | KeyCode::Char('k') => {
-                                app.log_scroll = app.log_scroll.saturating_sub(1);
-                            }
-                            KeyCode::Home => {
-                                app.log_scroll = 0;
-                            }
-                            KeyCode::End => {
-                                app.log_scroll = u16::MAX;
-                            }
-                            KeyCode::PageDown => {
-                                app.log_scroll = app.log_scroll.saturating_add(10);
-                            }
-                            KeyCode::PageUp => {
-                                app.log_scroll = app.log_scroll.saturating_sub(10);
-                            }
-                            KeyCode::Char('q') => {
-                                app.go_back();
-                            }
-                            _ => {}
-                        }
-                    }
-                    Screen::Activity => {
-                        match key.code {
-                            KeyCode::Down => app.next(),
-                            KeyCode::Up => app.previous(),
-                            KeyCode::Backspace => {
-                                app.go_back();
-                            }
-                            KeyCode::Char('r') => {
-                                app.fetch_activity(client).await?;
-                            }
-                            _ => {}
-                        }
+            if let Some(open) = &mut help {
+                if !open.handle_key(key, &app.keys) {
+                    help = None;
+                }
+                continue;
+            }
+
+            if let Some(open) = &mut history {
+                let toggled = app.keys.action(Scope::Global, key) == Some(KeyAction::Notifications);
+                if toggled || !open.handle_key(key, &mut app.notifications) {
+                    history = None;
+                }
+                continue;
+            }
+
+            // The palette takes all keys while it is open
+            if let Some(open) = &mut palette {
+                match open.handle_key(key, app) {
+                    Choice::Pending => {}
+                    Choice::Cancel => palette = None,
+                    Choice::Run(command) => {
+                        palette = None;
+                        command.run(&mut nav, &mut Ctx::new(app, tasks));
                     }
-                    Screen::Settings => {
-                        match key.code {
-                            KeyCode::Backspace => {
-                                app.go_back();
-                            }
-                            KeyCode::Char('c') => {
-                                // Reconfigure
-                                delete_config()?;
-                                app.should_quit = true;
-                                return Ok(());
-                            }
-                            KeyCode::Char('d') => {
-                                // Delete config and quit
-                                delete_config()?;
-                                app.should_quit = true;
-                                return Ok(());
-                            }
-                            _ => {}
-                        }
+                }
+                continue;
+            }
+
+            // Text fields get typed characters before the global bindings
+            let action = if nav.current().is_editing() && Key::is_text(key) {
+                None
+            } else {
+                app.keys.action(Scope::Global, key)
+            };
+            match action {
+                Some(KeyAction::Quit) => {
+                    app.should_quit = true;
+                    return Ok(());
+                }
+                Some(KeyAction::Help) => {
+                    help = Some(HelpOverlay::open(nav.current().scope(), nav.current().is_editing()));
+                    continue;
+                }
+                Some(KeyAction::Palette) => {
+                    palette = Some(Palette::open(&mut Ctx::new(app, tasks)));
+                    continue;
+                }
+                Some(KeyAction::Notifications) => {
+                    history = Some(HistoryPanel::open(&mut app.notifications));
+                    continue;
+                }
+                Some(KeyAction::Dismiss) if app.notifications.dismiss() => continue,
+                Some(KeyAction::HistoryBack) => {
+                    nav.go(Transition::Back, &mut Ctx::new(app, tasks));
+                    continue;
+                }
+                Some(KeyAction::HistoryForward) => {
+                    nav.go(Transition::Forward, &mut Ctx::new(app, tasks));
+                    continue;
+                }
+                // While something is loading, this cancels it
+                Some(KeyAction::CancelRequest) if !app.pending.is_empty() => {
+                    if let Some(label) = tasks.cancel_latest(app) {
+                        app.notifications.info(format!("Cancelled: {}", label));
                     }
-                    _ => {}
+                    continue;
                 }
-            } else if let Event::Mouse(_mouse) = event::read()? {
-                // Handle mouse events if needed, for now just ignore to prevent errors
+                _ => {}
             }
         }
+
+        // Everything else belongs to the current screen
+        let mut ctx = Ctx::new(app, tasks);
+        let transition = nav.current().handle_event(&event, &mut ctx)?;
+        nav.go(transition, &mut ctx);
+        if app.should_quit {
+            return Ok(());
+        }
     }
 }
@@ -28,6 +28,17 @@ pub struct Project {
     pub env_vars: Option<Vec<EnvVar>>,
 }
 
+impl Project {
+    /// Status of the most recent deployment, or "unknown" if there is none.
+    pub fn latest_status(&self) -> &str {
+        self.deployments
+            .as_ref()
+            .and_then(|deps| deps.first())
+            .map(|d| d.status.as_str())
+            .unwrap_or("unknown")
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Deployment {
     pub id: String,
@@ -40,9 +51,19 @@ pub struct Deployment {
     pub updated_at: String,
 }
 
+impl Deployment {
+    pub fn short_commit(&self) -> &str {
+        if self.commit.len() > 7 {
+            &self.commit[..7]
+        } else {
+            &self.commit
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct EnvVar {
-    #[serde(rename = "ID")]
+    #[serde(alias = "ID")]
     pub id: u32,
     pub project_id: String,
     pub key: String,
@@ -62,6 +83,25 @@ pub struct CreateProjectRequest {
     pub runtime: Option<String>,
 }
 
+/// Every field is written to the project, so unchanged ones have to be sent
+/// as they are. The port can't be changed.
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateProjectRequest {
+    pub name: String,
+    pub repo_url: String,
+    /// Empty keeps the current token.
+    pub git_token: String,
+    pub runtime: String,
+    pub install_command: String,
+    pub build_command: String,
+    pub start_command: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateEnvRequest {
+    pub env_vars: std::collections::HashMap<String, String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct RedeployRequest {
     pub commit: Option<String>,
@@ -69,13 +109,13 @@ pub struct RedeployRequest {
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Database {
-    #[serde(rename = "ID")]
+    #[serde(alias = "ID")]
     pub id: u32,
-    #[serde(rename = "CreatedAt")]
+    #[serde(alias = "CreatedAt")]
     pub created_at: String,
-    #[serde(rename = "UpdatedAt")]
+    #[serde(alias = "UpdatedAt")]
     pub updated_at: String,
-    #[serde(rename = "DeletedAt")]
+    #[serde(alias = "DeletedAt")]
     pub deleted_at: Option<String>,
     pub name: String,
     #[serde(rename = "type")]
@@ -87,14 +127,14 @@ pub struct Database {
     pub port: i32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct StorageStats {
     pub used: u64,
     pub total: u64,
     pub percent: f64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatabaseCredentials {
     pub username: String,
     pub password: String,
@@ -0,0 +1,170 @@
+use crate::models::{Database, DatabaseCredentials, Deployment, Project, StorageStats};
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns for humans
+    #[default]
+    Table,
+    /// A single JSON document (an array for listings)
+    Json,
+    /// A single YAML document
+    Yaml,
+    /// One JSON object per line
+    Ndjson,
+}
+
+/// Types that can be rendered as rows of a table.
+pub trait Tabular {
+    fn headers() -> &'static [&'static str];
+    fn row(&self) -> Vec<String>;
+}
+
+impl Tabular for Project {
+    fn headers() -> &'static [&'static str] {
+        &["ID", "NAME", "STATUS", "PORT", "RUNTIME", "REPOSITORY"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.name.clone(),
+            self.latest_status().to_string(),
+            self.port.to_string(),
+            if self.runtime.is_empty() { "auto".to_string() } else { self.runtime.clone() },
+            self.repo_url.clone(),
+        ]
+    }
+}
+
+impl Tabular for Deployment {
+    fn headers() -> &'static [&'static str] {
+        &["ID", "PROJECT", "STATUS", "COMMIT", "CREATED"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.project_id.clone(),
+            self.status.clone(),
+            self.short_commit().to_string(),
+            self.created_at.clone(),
+        ]
+    }
+}
+
+impl Tabular for Database {
+    fn headers() -> &'static [&'static str] {
+        &["ID", "NAME", "TYPE", "STATUS", "PORT", "SIZE (MB)"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.name.clone(),
+            self.db_type.clone(),
+            self.status.clone(),
+            self.port.to_string(),
+            format!("{:.1}", self.size_mb),
+        ]
+    }
+}
+
+impl Tabular for StorageStats {
+    fn headers() -> &'static [&'static str] {
+        &["USED", "TOTAL", "PERCENT"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let gb = |bytes: u64| format!("{:.2} GB", bytes as f64 / 1024.0 / 1024.0 / 1024.0);
+        vec![gb(self.used), gb(self.total), format!("{:.1}%", self.percent)]
+    }
+}
+
+impl Tabular for DatabaseCredentials {
+    fn headers() -> &'static [&'static str] {
+        &["USERNAME", "PASSWORD", "URI", "PUBLIC URI"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.username.clone(),
+            self.password.clone(),
+            self.uri.clone(),
+            self.public_uri.clone(),
+        ]
+    }
+}
+
+/// Prints a collection in the requested format.
+pub fn print_list<T: Serialize + Tabular>(format: OutputFormat, items: &[T]) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = items.iter().map(Tabular::row).collect();
+            print!("{}", render_table(T::headers(), &rows));
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(items)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(items)?),
+        OutputFormat::Ndjson => {
+            for item in items {
+                println!("{}", serde_json::to_string(item)?);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints a single record. Tables are shown as one field per line.
+pub fn print_item<T: Serialize + Tabular>(format: OutputFormat, item: &T) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            let headers = T::headers();
+            let width = headers.iter().map(|h| h.len()).max().unwrap_or(0);
+            for (header, value) in headers.iter().zip(item.row()) {
+                println!("{:<width$}  {}", header, value, width = width);
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(item)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(item)?),
+        OutputFormat::Ndjson => println!("{}", serde_json::to_string(item)?),
+    }
+
+    Ok(())
+}
+
+/// Formats rows into left-aligned columns padded to the widest cell.
+pub fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if i < widths.len() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let last = cells.len().saturating_sub(1);
+        let mut line = String::new();
+        for (i, cell) in cells.into_iter().enumerate() {
+            if i == last {
+                line.push_str(cell);
+            } else {
+                let pad = widths[i] - cell.chars().count();
+                line.push_str(cell);
+                line.push_str(&" ".repeat(pad + 2));
+            }
+        }
+        line.push('\n');
+        line
+    };
+
+    let mut out = format_row(headers.to_vec());
+    for row in rows {
+        out.push_str(&format_row(row.iter().map(String::as_str).collect()));
+    }
+    out
+}
@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Asks a yes/no question on the terminal. Defaults to "no".
+///
+/// Fails when stdin is not a terminal so scripts don't hang waiting for input;
+/// callers should offer a `--yes` flag for that case.
+pub fn confirm(question: &str) -> Result<bool> {
+    let answer = ask_to_confirm(question, "[y/N]")?;
+    Ok(matches!(answer.to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Asks the user to type `name` before something irreversible happens to it.
+pub fn confirm_name(question: &str, name: &str) -> Result<bool> {
+    let answer = ask_to_confirm(question, &format!("Type {} to confirm:", name))?;
+    Ok(answer == name)
+}
+
+/// Asks the user to pick one of `choices`, each given as a key and a label.
+/// An empty answer picks the first choice.
+pub fn choose(question: &str, choices: &[(char, &str)]) -> Result<char> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("{} No terminal to ask on", question);
+    }
+
+    let options: Vec<String> = choices
+        .iter()
+        .map(|(key, label)| format!("[{}] {}", key, label))
+        .collect();
+
+    loop {
+        let Some(answer) = read_answer(&format!("{} {}", question, options.join("  ")))? else {
+            anyhow::bail!("No answer given");
+        };
+
+        let answer = answer.to_lowercase();
+        if answer.is_empty() {
+            return Ok(choices[0].0);
+        }
+        if let Some((key, _)) = choices.iter().find(|(key, _)| answer.starts_with(*key)) {
+            return Ok(*key);
+        }
+    }
+}
+
+/// The answer to a confirmation, refusing when there's no terminal to ask on.
+/// No answer at all counts as an empty one.
+fn ask_to_confirm(question: &str, prompt: &str) -> Result<String> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("{} Refusing to continue without confirmation (pass --yes)", question);
+    }
+    Ok(read_answer(&format!("{} {}", question, prompt))?.unwrap_or_default())
+}
+
+/// Shows `prompt` and reads the line typed in reply, trimmed. `None` when
+/// the input has ended.
+fn read_answer(prompt: &str) -> Result<Option<String>> {
+    eprint!("{} ", prompt);
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    let read = io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+
+    Ok((read > 0).then(|| answer.trim().to_string()))
+}
+
+/// Asks for a secret, such as an API key, without echoing what is typed.
+pub fn secret(question: &str) -> Result<String> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("{}: no terminal to ask on", question);
+    }
+
+    eprint!("{}: ", question);
+    io::stderr().flush()?;
+
+    terminal::enable_raw_mode().context("Failed to read answer")?;
+    let answer = read_hidden();
+    terminal::disable_raw_mode()?;
+    eprintln!();
+
+    answer
+}
+
+fn read_hidden() -> Result<String> {
+    let mut answer = String::new();
+    loop {
+        let Event::Key(key) = event::read().context("Failed to read answer")? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(answer.trim().to_string()),
+            KeyCode::Backspace => {
+                answer.pop();
+            }
+            KeyCode::Char('c' | 'd') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                anyhow::bail!("Cancelled");
+            }
+            KeyCode::Char(c) => answer.push(c),
+            _ => {}
+        }
+    }
+}
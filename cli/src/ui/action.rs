@@ -0,0 +1,250 @@
+use crate::api::{ApiClient, ApiResult};
+use crate::models::{
+    CreateProjectRequest, Database, DatabaseCredentials, Deployment, Project, StorageStats, UpdateProjectRequest, User,
+};
+use crate::ui::App;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Server work requested by a key handler. Each action runs on its own
+/// background task so the UI keeps drawing and taking input meanwhile.
+pub enum Action {
+    FetchProjects,
+    FetchProjectDetail(String),
+    FetchActivity,
+    FetchStorage,
+    CreateProject(CreateProjectRequest),
+    /// Save a project's settings, then redeploy it if `redeploy` is set.
+    /// `stop_first` stops the running container before saving; it's named
+    /// after the project, so after a rename it couldn't be found anymore.
+    UpdateProject { id: String, request: UpdateProjectRequest, redeploy: bool, stop_first: bool },
+    Redeploy(String),
+    StopProject(String),
+    FetchDatabaseCredentials(u32),
+    CreateDatabase { name: String, db_type: String },
+    DeleteDatabase(u32),
+    StopDatabase(u32),
+    RestartDatabase(u32),
+    /// Validate another profile; the client is only adopted if it works.
+    SwitchProfile { name: String, client: ApiClient },
+}
+
+impl Action {
+    pub fn label(&self) -> String {
+        match self {
+            Action::FetchProjects => "Fetching projects".to_string(),
+            Action::FetchProjectDetail(_) => "Fetching project".to_string(),
+            Action::FetchActivity => "Fetching activity".to_string(),
+            Action::FetchStorage => "Fetching storage data".to_string(),
+            Action::CreateProject(request) => format!("Creating project {}", request.name),
+            Action::UpdateProject { request, .. } => format!("Saving settings of {}", request.name),
+            Action::Redeploy(_) => "Redeploying".to_string(),
+            Action::StopProject(_) => "Stopping project".to_string(),
+            Action::FetchDatabaseCredentials(_) => "Fetching credentials".to_string(),
+            Action::CreateDatabase { name, .. } => format!("Creating database {}", name),
+            Action::DeleteDatabase(_) => "Deleting database".to_string(),
+            Action::StopDatabase(_) => "Stopping database".to_string(),
+            Action::RestartDatabase(_) => "Restarting database".to_string(),
+            Action::SwitchProfile { name, .. } => format!("Connecting with profile {}", name),
+        }
+    }
+
+    /// Reads that can simply be replaced by a newer request of the same kind.
+    fn is_refresh(&self) -> bool {
+        matches!(
+            self,
+            Action::FetchProjects
+                | Action::FetchProjectDetail(_)
+                | Action::FetchActivity
+                | Action::FetchStorage
+        )
+    }
+
+    async fn run(self, client: ApiClient) -> ActionResult {
+        match self {
+            Action::FetchProjects => ActionResult::Projects(client.list_projects().await),
+            Action::FetchProjectDetail(id) => {
+                let result = client.get_project(&id).await;
+                ActionResult::ProjectDetail(id, result)
+            }
+            Action::FetchActivity => ActionResult::Activity(client.get_activity().await),
+            Action::FetchStorage => {
+                let (databases, stats) = tokio::join!(client.list_databases(), client.get_storage_stats());
+                ActionResult::Storage(databases, stats)
+            }
+            Action::CreateProject(request) => ActionResult::ProjectCreated(client.create_project(request).await),
+            Action::UpdateProject { id, request, redeploy, stop_first } => {
+                let stopped = if stop_first { client.stop_project(&id).await.map(|_| ()) } else { Ok(()) };
+                let result = match stopped {
+                    Ok(()) => client.update_project(&id, request).await,
+                    Err(e) => Err(e),
+                };
+                ActionResult::ProjectUpdated { id, redeploy, result }
+            }
+            Action::Redeploy(id) => {
+                let result = client.redeploy_project(&id, None).await.map(|_| ());
+                ActionResult::Redeployed(id, result)
+            }
+            Action::StopProject(id) => {
+                let result = client.stop_project(&id).await.map(|_| ());
+                ActionResult::ProjectStopped(id, result)
+            }
+            Action::FetchDatabaseCredentials(id) => {
+                let result = client.get_database_credentials(id).await;
+                ActionResult::DatabaseCredentials(id, result)
+            }
+            Action::CreateDatabase { name, db_type } => {
+                ActionResult::DatabaseCreated(client.create_database(name, db_type).await.map(|_| ()))
+            }
+            Action::DeleteDatabase(id) => ActionResult::DatabaseDeleted(client.delete_database(id).await),
+            Action::StopDatabase(id) => ActionResult::DatabaseStopped(client.stop_database(id).await),
+            Action::RestartDatabase(id) => ActionResult::DatabaseRestarted(client.restart_database(id).await),
+            Action::SwitchProfile { name, client } => {
+                let result = client.validate_connection().await;
+                ActionResult::ProfileSwitched { name, client, result }
+            }
+        }
+    }
+}
+
+/// What a finished action sends back to the loop, to be applied to `App`.
+pub enum ActionResult {
+    Projects(ApiResult<Vec<Project>>),
+    ProjectDetail(String, ApiResult<Project>),
+    Activity(ApiResult<Vec<Deployment>>),
+    Storage(ApiResult<Vec<Database>>, ApiResult<StorageStats>),
+    ProjectCreated(ApiResult<Project>),
+    ProjectUpdated { id: String, redeploy: bool, result: ApiResult<Project> },
+    Redeployed(String, ApiResult<()>),
+    ProjectStopped(String, ApiResult<()>),
+    DatabaseCredentials(u32, ApiResult<DatabaseCredentials>),
+    DatabaseCreated(ApiResult<()>),
+    DatabaseDeleted(ApiResult<()>),
+    DatabaseStopped(ApiResult<()>),
+    DatabaseRestarted(ApiResult<()>),
+    ProfileSwitched { name: String, client: ApiClient, result: ApiResult<User> },
+}
+
+/// An action still in flight, as shown by the spinner.
+pub struct PendingAction {
+    pub id: u64,
+    pub label: String,
+    pub started: Instant,
+    refresh_key: Option<std::mem::Discriminant<Action>>,
+}
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+impl PendingAction {
+    pub fn spinner(&self) -> &'static str {
+        SPINNER[(self.started.elapsed().as_millis() / 80) as usize % SPINNER.len()]
+    }
+}
+
+/// Runs actions on background tasks and collects their results.
+pub struct Tasks {
+    client: ApiClient,
+    tx: mpsc::UnboundedSender<(u64, ActionResult)>,
+    rx: mpsc::UnboundedReceiver<(u64, ActionResult)>,
+    handles: Vec<(u64, JoinHandle<()>)>,
+    next_id: u64,
+}
+
+impl Tasks {
+    pub fn new(client: ApiClient) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            client,
+            tx,
+            rx,
+            handles: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn client(&self) -> &ApiClient {
+        &self.client
+    }
+
+    /// Talks to another server from now on. Requests still running against
+    /// the old one are cancelled, so their results can't end up in the new
+    /// profile's data or cache.
+    pub fn switch_client(&mut self, app: &mut App, client: ApiClient) {
+        self.client = client;
+        let stale: Vec<u64> = app.pending.iter().map(|p| p.id).collect();
+        for id in stale {
+            self.abort(app, id);
+        }
+    }
+
+    /// Starts `action` in the background. A refresh replaces one of the same
+    /// kind that is still running instead of piling up behind it.
+    pub fn dispatch(&mut self, app: &mut App, action: Action) {
+        if app.offline && !matches!(action, Action::SwitchProfile { .. }) {
+            app.serve_offline(&action);
+            return;
+        }
+
+        let refresh_key = action.is_refresh().then(|| std::mem::discriminant(&action));
+        if let Some(key) = refresh_key {
+            let stale: Vec<u64> = app
+                .pending
+                .iter()
+                .filter(|p| p.refresh_key == Some(key))
+                .map(|p| p.id)
+                .collect();
+            for id in stale {
+                self.abort(app, id);
+            }
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        app.pending.push(PendingAction {
+            id,
+            label: action.label(),
+            started: Instant::now(),
+            refresh_key,
+        });
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        let handle = tokio::spawn(async move {
+            let result = action.run(client).await;
+            let _ = tx.send((id, result));
+        });
+        self.handles.push((id, handle));
+    }
+
+    /// Cancels the most recently started action. Returns its label.
+    pub fn cancel_latest(&mut self, app: &mut App) -> Option<String> {
+        let pending = app.pending.last()?;
+        let (id, label) = (pending.id, pending.label.clone());
+        self.abort(app, id);
+        Some(label)
+    }
+
+    fn abort(&mut self, app: &mut App, id: u64) {
+        if let Some(pos) = self.handles.iter().position(|(handle_id, _)| *handle_id == id) {
+            self.handles.remove(pos).1.abort();
+        }
+        app.pending.retain(|p| p.id != id);
+    }
+
+    /// Waits for the next finished action.
+    pub async fn next(&mut self) -> (u64, ActionResult) {
+        // `self` holds a sender, so the channel never closes
+        self.rx.recv().await.expect("action channel closed")
+    }
+
+    /// Marks an action done. Returns false if it was cancelled, in which
+    /// case its result should be dropped.
+    pub fn finish(&mut self, app: &mut App, id: u64) -> bool {
+        self.handles.retain(|(handle_id, _)| *handle_id != id);
+        let was_pending = app.pending.iter().any(|p| p.id == id);
+        app.pending.retain(|p| p.id != id);
+        was_pending
+    }
+}
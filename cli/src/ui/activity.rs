@@ -1,16 +1,98 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
-use crate::ui::app::App;
+use anyhow::Result;
+use crossterm::event::Event;
+use crate::ui::action::Action;
+use crate::ui::app::{App, Screen};
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::list_filter::{ListFilter, SortKey};
 
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+const SORTS: &[SortKey] = &[SortKey::Name, SortKey::Status, SortKey::Port];
+
+pub struct ActivityView {
+    filter: ListFilter,
+}
+
+impl ActivityView {
+    pub fn new() -> Self {
+        Self { filter: ListFilter::new(SORTS) }
+    }
+}
+
+impl Component for ActivityView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        render(f, area, app, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let rows = self.filter.apply(&ctx.app.activity, ctx.app);
+        let action = match event {
+            Event::Key(key) => {
+                if self.filter.handle_key(key, &ctx.app.keys) {
+                    return Ok(Transition::Stay);
+                }
+                ctx.app.keys.action(Scope::Activity, key)
+            }
+            Event::Mouse(mouse) => self.filter.handle_mouse(mouse, &rows),
+            _ => None,
+        };
+        let Some(action) = action else {
+            return Ok(Transition::Stay);
+        };
+
+        if self.filter.navigate(action, &rows) {
+            return Ok(Transition::Stay);
+        }
+        match action {
+            KeyAction::Open => {
+                if let Some(deployment) = self.filter.selected(&rows) {
+                    return Ok(Transition::Open(Screen::DeploymentLogs(deployment.id.clone())));
+                }
+            }
+            KeyAction::Back => return Ok(Transition::Back),
+            KeyAction::Refresh => ctx.dispatch(Action::FetchActivity),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Activity
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        if self.filter.is_editing() {
+            return self.filter.keymap(keys);
+        }
+        let mut hints = keys.pair_hint(Scope::Activity, KeyAction::Up, KeyAction::Down, "Navigate");
+        hints.extend(keys.hints(Scope::Activity, &[KeyAction::Open, KeyAction::Back, KeyAction::Refresh]));
+        hints.extend(self.filter.keymap(keys));
+        hints.extend(keys.hints(Scope::Activity, &[KeyAction::Quit]));
+        hints
+    }
+
+    fn focus(&mut self, id: &str, _app: &App) {
+        self.filter.select(id.to_string());
+    }
+
+    fn is_editing(&self) -> bool {
+        self.filter.is_editing()
+    }
+
+    fn on_enter(&mut self, ctx: &mut Ctx) {
+        ctx.dispatch(Action::FetchActivity);
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App, view: &mut ActivityView) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
         .split(area);
 
     // Header
@@ -20,8 +102,10 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(header, chunks[0]);
 
     // Activity list
-    let items: Vec<ListItem> = app
-        .activity
+    let list_area = view.filter.render(f, chunks[1]);
+    let rows = view.filter.apply(&app.activity, app);
+    let selected = view.filter.selected_index(&rows);
+    let items: Vec<ListItem> = rows
         .iter()
         .enumerate()
         .map(|(i, d)| {
@@ -56,7 +140,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 timestamp
             );
 
-            let style = if i == app.selected_index {
+            let style = if i == selected {
                 Style::default()
                     .fg(status_color)
                     .add_modifier(Modifier::BOLD)
@@ -73,45 +157,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Deployments ({})", app.activity.len()))
-        )
-        .highlight_symbol(">> ");
-
-    f.render_widget(list, chunks[1]);
-
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-            Span::raw(" Navigate | "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(" View Logs | "),
-            Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-            Span::raw(" Back | "),
-            Span::styled("r", Style::default().fg(Color::Yellow)),
-            Span::raw(" Refresh | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::DarkGray));
-    f.render_widget(footer, chunks[2]);
-
-    // Show error if any
-    if let Some(error) = &app.error {
-        let error_area = Rect {
-            x: area.width / 4,
-            y: area.height / 2 - 2,
-            width: area.width / 2,
-            height: 5,
-        };
-        
-        let error_widget = Paragraph::new(error.as_str())
-            .style(Style::default().fg(Color::Red))
-            .block(Block::default().borders(Borders::ALL).title("Error"));
-        f.render_widget(error_widget, error_area);
-    }
+                .title(view.filter.title("Deployments", rows.len(), app.activity.len()))
+        );
+
+    view.filter.render_list(f, list, list_area, Some(selected));
 }
@@ -0,0 +1,225 @@
+use ratatui::style::{Color, Modifier, Style};
+
+/// Columns a tab moves to the next multiple of.
+const TAB_WIDTH: usize = 8;
+
+/// Text with the escape codes removed, plus the style that applies from
+/// each byte offset on.
+pub struct Styled {
+    pub text: String,
+    pub runs: Vec<(usize, Style)>,
+}
+
+/// Removes the escape sequences from one line of terminal output, turning
+/// SGR colour codes into styles. `style` is the style in effect where the
+/// line starts and is left at the one in effect where it ends, since
+/// colours carry over into the next line. A carriage return starts the
+/// line over, the way progress bars redraw themselves.
+pub fn parse(raw: &str, style: &mut Style) -> Styled {
+    let mut out = Styled::new(*style);
+    out.push_raw(raw, style);
+    out
+}
+
+impl Styled {
+    pub fn new(style: Style) -> Self {
+        Self { text: String::new(), runs: vec![(0, style)] }
+    }
+
+    /// Parses more of a line onto the end, like [`parse`], for a line that
+    /// comes in bit by bit. Returns how much of `raw` was used: an escape
+    /// sequence or carriage return cut off at the end is left for when the
+    /// rest of it comes in.
+    pub fn push_raw(&mut self, raw: &str, style: &mut Style) -> usize {
+        let mut chars = raw.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            match c {
+                '\x1b' => {
+                    let complete = match chars.next().map(|(_, c)| c) {
+                        // CSI: parameters, then a final byte in @..~
+                        Some('[') => {
+                            let mut params = String::new();
+                            let mut done = false;
+                            for (_, c) in chars.by_ref() {
+                                if ('@'..='~').contains(&c) {
+                                    if c == 'm' {
+                                        apply_sgr(&params, style);
+                                        self.restyle(*style);
+                                    }
+                                    done = true;
+                                    break;
+                                }
+                                params.push(c);
+                            }
+                            done
+                        }
+                        // OSC (titles, hyperlinks): up to BEL or ESC \
+                        Some(']') => {
+                            let mut done = false;
+                            while let Some((_, c)) = chars.next() {
+                                if c == '\x07' || (c == '\x1b' && chars.next_if(|&(_, c)| c == '\\').is_some()) {
+                                    done = true;
+                                    break;
+                                }
+                            }
+                            done
+                        }
+                        // Character set designation, e.g. `ESC ( B` from
+                        // `tput sgr0`: one more byte names the set
+                        Some('(' | ')' | '*' | '+') => chars.next().is_some(),
+                        Some(_) => true,
+                        None => false,
+                    };
+                    if !complete {
+                        return start;
+                    }
+                }
+                '\r' if chars.peek().is_none() => return start,
+                '\r' => {
+                    self.text.clear();
+                    self.runs = vec![(0, *style)];
+                }
+                '\t' => {
+                    let column = self.text.chars().count();
+                    self.text.extend(std::iter::repeat_n(' ', TAB_WIDTH - column % TAB_WIDTH));
+                }
+                c if c.is_control() => {}
+                c => self.text.push(c),
+            }
+        }
+        raw.len()
+    }
+
+    fn restyle(&mut self, style: Style) {
+        match self.runs.last_mut() {
+            Some((start, last)) if *start == self.text.len() => *last = style,
+            _ => self.runs.push((self.text.len(), style)),
+        }
+    }
+}
+
+fn apply_sgr(params: &str, style: &mut Style) {
+    let mut codes = params.split([';', ':']).map(|p| p.parse::<u16>().unwrap_or(0));
+    // `ESC[m` is a reset
+    let mut next = Some(codes.next().unwrap_or(0));
+
+    while let Some(code) = next {
+        *style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 | 6 => style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            8 => style.add_modifier(Modifier::HIDDEN),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            25 => style.remove_modifier(Modifier::SLOW_BLINK),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            28 => style.remove_modifier(Modifier::HIDDEN),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(Color::Indexed((code - 30) as u8)),
+            38 => match extended_color(&mut codes) {
+                Some(color) => style.fg(color),
+                None => *style,
+            },
+            39 => style.fg(Color::Reset),
+            40..=47 => style.bg(Color::Indexed((code - 40) as u8)),
+            48 => match extended_color(&mut codes) {
+                Some(color) => style.bg(color),
+                None => *style,
+            },
+            49 => style.bg(Color::Reset),
+            90..=97 => style.fg(Color::Indexed((code - 90 + 8) as u8)),
+            100..=107 => style.bg(Color::Indexed((code - 100 + 8) as u8)),
+            _ => *style,
+        };
+        next = codes.next();
+    }
+}
+
+/// The rest of a 38/48 code: `5;n` for the 256 colour palette or `2;r;g;b`.
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    let byte = |code: Option<u16>| code.map(|c| c.min(255) as u8);
+    match codes.next()? {
+        5 => byte(codes.next()).map(Color::Indexed),
+        2 => Some(Color::Rgb(byte(codes.next())?, byte(codes.next())?, byte(codes.next())?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(raw: &str) -> String {
+        parse(raw, &mut Style::default()).text
+    }
+
+    #[test]
+    fn sgr_codes_become_runs() {
+        let mut style = Style::default();
+        let out = parse("a\x1b[1;31mb\x1b[0mc", &mut style);
+        assert_eq!(out.text, "abc");
+        assert_eq!(out.runs, [
+            (0, Style::default()),
+            (1, Style::default().add_modifier(Modifier::BOLD).fg(Color::Indexed(1))),
+            (2, Style::default()),
+        ]);
+    }
+
+    #[test]
+    fn extended_colors() {
+        let mut style = Style::default();
+        parse("\x1b[38;5;208;48;2;1;2;3m", &mut style);
+        assert_eq!(style, Style::default().fg(Color::Indexed(208)).bg(Color::Rgb(1, 2, 3)));
+
+        parse("\x1b[22;39;49m\x1b[92m", &mut style);
+        assert_eq!(style.fg, Some(Color::Indexed(10)));
+        assert_eq!(style.bg, Some(Color::Reset));
+    }
+
+    #[test]
+    fn style_carries_over_lines() {
+        let mut style = Style::default();
+        parse("\x1b[33mwarn", &mut style);
+        let next = parse("still yellow\x1b[m", &mut style);
+        assert_eq!(next.runs[0], (0, Style::default().fg(Color::Indexed(3))));
+        assert_eq!(style, Style::default());
+    }
+
+    #[test]
+    fn charset_designators_are_dropped() {
+        // `tput sgr0` on xterm
+        assert_eq!(plain("\x1b(B\x1b[mBuild"), "Build");
+        assert_eq!(plain("a\x1b)0b\x1b*Bc\x1b+Bd"), "abcd");
+    }
+
+    #[test]
+    fn other_sequences_are_dropped() {
+        assert_eq!(plain("\x1b]0;title\x07a\x1b]8;;http://x\x1b\\b\x1b[2Kc\x1b=d"), "abcd");
+    }
+
+    #[test]
+    fn carriage_return_starts_over() {
+        assert_eq!(plain("10%\r50%\r100%"), "100%");
+        assert_eq!(plain("done\r"), "done");
+        assert_eq!(plain("a\tbc\td"), "a       bc      d");
+    }
+
+    #[test]
+    fn push_raw_leaves_cut_off_sequences() {
+        let mut style = Style::default();
+        let mut out = Styled::new(style);
+        assert_eq!(out.push_raw("ab\x1b[3", &mut style), 2);
+        assert_eq!(out.push_raw("\x1b[31mc\r", &mut style), 6);
+        assert_eq!(out.push_raw("\x1b(", &mut style), 0);
+        assert_eq!(out.push_raw("\rd", &mut style), 2);
+        assert_eq!(out.text, "d");
+        assert_eq!(style, Style::default().fg(Color::Indexed(1)));
+    }
+}
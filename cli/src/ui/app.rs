@@ -1,11 +1,15 @@
-use crate::api::ApiClient;
-use crate::models::{Deployment, Project, User, Database, StorageStats, DatabaseCredentials};
-use crate::ui::{CreateProjectState, CreateDatabaseState};
-use anyhow::Result;
+use crate::api::ApiResult;
+use crate::cache::{self, Snapshot};
+use crate::config::{save_config, Config};
+use crate::models::{Deployment, Project, User, Database, StorageStats};
+use crate::ui::action::{Action, ActionResult, PendingAction};
+use crate::ui::confirm::Confirmation;
+use crate::ui::failure_analysis::FailureRules;
+use crate::ui::keymap::Keymap;
+use crate::ui::notifications::Notifications;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Screen {
-    Setup,
     Projects,
     CreateProject,
     CreateDatabase,
@@ -26,186 +30,296 @@ pub struct App {
     pub selected_project: Option<Project>,
     pub activity: Vec<Deployment>,
     pub user: Option<User>,
-    pub message: String,
-    pub error: Option<String>,
-    pub selected_index: usize,
+    pub notifications: Notifications,
     pub should_quit: bool,
-    pub create_project_state: CreateProjectState,
-    pub live_logs: String,
-    pub log_scroll: u16,
     pub databases: Vec<Database>,
-    pub selected_database: Option<Database>,
     pub storage_stats: Option<StorageStats>,
-    pub create_database_state: CreateDatabaseState,
-    pub show_db_credentials: bool,
-    pub db_credentials: Option<DatabaseCredentials>,
+    pub config: Config,
+    pub active_profile: String,
+    pub reconfigure: bool,
+    /// Set when the server was unreachable at startup and cached data is shown.
+    pub offline: bool,
+    pub cached_at: String,
+    /// Background actions still running, newest last.
+    pub pending: Vec<PendingAction>,
+    /// A destructive action shown in a modal until it is confirmed.
+    pub confirm: Option<Confirmation>,
+    pub keys: Keymap,
+    pub failure_rules: FailureRules,
 }
 
 impl App {
     pub fn new() -> Self {
         Self {
-            screen: Screen::Setup,
+            screen: Screen::Projects,
             projects: Vec::new(),
             selected_project: None,
             activity: Vec::new(),
             user: None,
-            message: String::new(),
-            error: None,
-            selected_index: 0,
+            notifications: Notifications::default(),
             should_quit: false,
-            create_project_state: CreateProjectState::new(),
-            live_logs: String::new(),
-            log_scroll: 0,
             databases: Vec::new(),
-            selected_database: None,
             storage_stats: None,
-            create_database_state: CreateDatabaseState::new(),
-            show_db_credentials: false,
-            db_credentials: None,
+            config: Config::default(),
+            active_profile: String::new(),
+            reconfigure: false,
+            offline: false,
+            cached_at: String::new(),
+            pending: Vec::new(),
+            confirm: None,
+            keys: Keymap::default(),
+            failure_rules: FailureRules::default(),
         }
     }
 
-    pub async fn fetch_projects(&mut self, client: &ApiClient) -> Result<()> {
-        self.message = "Fetching projects...".to_string();
-        self.error = None;
-        
-        match client.list_projects().await {
-            Ok(projects) => {
-                self.projects = projects;
-                self.message = format!("Loaded {} projects", self.projects.len());
-            }
-            Err(e) => {
-                self.error = Some(format!("Failed to fetch projects: {}", e));
-            }
+    /// Shows cached data and blocks anything that would change the server.
+    pub fn enter_offline_mode(&mut self, snapshot: Snapshot) {
+        self.offline = true;
+        self.cached_at = snapshot.saved_at;
+        self.projects = snapshot.projects;
+        self.activity = snapshot.activity;
+        self.databases = snapshot.databases;
+        self.storage_stats = snapshot.storage_stats;
+        self.notifications.warning(self.offline_message());
+    }
+
+    fn offline_message(&self) -> String {
+        format!("Offline - showing data cached at {}", self.cached_at)
+    }
+
+    /// Returns false and shows an error when in offline mode.
+    pub fn require_online(&mut self) -> bool {
+        if self.offline {
+            self.notifications.warning("Offline mode is read-only. Reconnect from Settings (s).");
         }
-        
-        Ok(())
+        !self.offline
     }
 
-    pub async fn fetch_project_detail(&mut self, client: &ApiClient, id: &str) -> Result<()> {
-        self.error = None;
-        
-        match client.get_project(id).await {
-            Ok(project) => {
-                self.selected_project = Some(project);
-                self.message.clear();
-            }
-            Err(e) => {
-                self.error = Some(format!("Failed to fetch project: {}", e));
-            }
+    fn save_cache(&self) {
+        if self.active_profile.is_empty() {
+            return;
         }
-        
-        Ok(())
+
+        let snapshot = Snapshot {
+            saved_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            projects: self.projects.clone(),
+            activity: self.activity.clone(),
+            databases: self.databases.clone(),
+            storage_stats: self.storage_stats.clone(),
+        };
+        // The cache is only a fallback, so failing to write it is not worth reporting
+        let _ = cache::save(&self.active_profile, snapshot);
     }
 
-    pub async fn fetch_activity(&mut self, client: &ApiClient) -> Result<()> {
-        self.message = "Fetching activity...".to_string();
-        self.error = None;
-        
-        match client.get_activity().await {
-            Ok(activity) => {
-                self.activity = activity;
-                self.message = format!("Loaded {} deployments", self.activity.len());
-            }
-            Err(e) => {
-                self.error = Some(format!("Failed to fetch activity: {}", e));
+    /// Answers reads from cached data while offline.
+    pub fn serve_offline(&mut self, action: &Action) {
+        match action {
+            Action::FetchProjectDetail(id) => {
+                self.selected_project = self.projects.iter().find(|p| &p.id == id).cloned();
             }
+            _ => self.notifications.info(self.offline_message()),
         }
-        
-        Ok(())
     }
 
-    pub async fn fetch_storage_data(&mut self, client: &ApiClient) -> Result<()> {
-        self.message = "Fetching storage data...".to_string();
-        self.error = None;
-        
-        match client.list_databases().await {
-            Ok(databases) => {
-                self.databases = databases;
+    /// Applies the result of a background action. Returns follow-up actions,
+    /// such as refreshing a list after something in it changed.
+    pub fn apply(&mut self, result: ActionResult) -> Vec<Action> {
+        match result {
+            ActionResult::Projects(Ok(projects)) => {
+                let latest = projects.iter().filter_map(|p| p.deployments.as_ref()?.first());
+                self.notify_finished(latest);
+                self.projects = projects;
+                self.save_cache();
             }
-            Err(e) => {
-                self.error = Some(format!("Failed to fetch databases: {}", e));
+            ActionResult::Projects(Err(e)) => {
+                self.notifications.error(e.describe("Failed to fetch projects"));
             }
-        }
-        
-        match client.get_storage_stats().await {
-            Ok(stats) => {
-                self.storage_stats = Some(stats);
-                self.message = format!("Loaded {} databases", self.databases.len());
+            ActionResult::ProjectDetail(id, result) => {
+                // The user may have moved on to another project meanwhile
+                let still_viewing = match &self.screen {
+                    Screen::ProjectDetail(current) | Screen::ProjectSettings(current) => *current == id,
+                    // The logs screen polls for the status of its deployment
+                    Screen::DeploymentLogs(deployment) => result
+                        .as_ref()
+                        .is_ok_and(|project| project.deployments.iter().flatten().any(|d| d.id == *deployment)),
+                    _ => false,
+                };
+                if still_viewing {
+                    match result {
+                        Ok(project) => {
+                            self.notify_finished(project.deployments.iter().flatten());
+                            self.selected_project = Some(project);
+                        }
+                        Err(e) => self.notifications.error(e.describe("Failed to fetch project")),
+                    }
+                }
             }
-            Err(e) => {
-                self.error = Some(format!("Failed to fetch storage stats: {}", e));
+            ActionResult::Activity(Ok(activity)) => {
+                self.notify_finished(activity.iter());
+                self.activity = activity;
+                self.save_cache();
+            }
+            ActionResult::Activity(Err(e)) => {
+                self.notifications.error(e.describe("Failed to fetch activity"));
+            }
+            ActionResult::Storage(databases, stats) => {
+                match databases {
+                    Ok(databases) => self.databases = databases,
+                    Err(e) => self.notifications.error(e.describe("Failed to fetch databases")),
+                }
+                match stats {
+                    Ok(stats) => {
+                        self.storage_stats = Some(stats);
+                        self.save_cache();
+                    }
+                    Err(e) => self.notifications.error(e.describe("Failed to fetch storage stats")),
+                }
+            }
+            ActionResult::ProjectCreated(Ok(_)) => {
+                self.notifications.success("Project created");
+                return vec![Action::FetchProjects];
+            }
+            ActionResult::ProjectUpdated { id, redeploy, result: Ok(project) } => {
+                self.notifications.success(format!("Settings of {} saved", project.name));
+                let refresh = if redeploy { Action::Redeploy(id) } else { Action::FetchProjectDetail(id) };
+                return vec![Action::FetchProjects, refresh];
+            }
+            ActionResult::Redeployed(id, Ok(())) => {
+                self.notifications.success(format!("Redeploy of {} started", self.project_name(&id)));
+                return vec![Action::FetchProjectDetail(id)];
+            }
+            ActionResult::Redeployed(_, Err(e)) => {
+                self.notifications.error(e.describe("Redeploy failed"));
+            }
+            ActionResult::ProjectStopped(id, Ok(())) => {
+                self.notifications.success(format!("{} stopped", self.project_name(&id)));
+                return vec![Action::FetchProjectDetail(id)];
+            }
+            ActionResult::ProjectStopped(_, Err(e)) => {
+                self.notifications.error(e.describe("Stop failed"));
+            }
+            // Only the screen that asked for these has a use for them
+            ActionResult::ProjectCreated(Err(_)) | ActionResult::DatabaseCredentials(..) => {}
+            // The settings screen may have been left while saving
+            ActionResult::ProjectUpdated { result: Err(e), .. } => {
+                self.notifications.error(e.describe("Failed to save settings"));
+            }
+            ActionResult::DatabaseCreated(Ok(())) => {
+                self.notifications.success("Database created");
+                return vec![Action::FetchStorage];
+            }
+            ActionResult::DatabaseCreated(Err(e)) => {
+                self.notifications.error(e.describe("Failed to create database"));
+            }
+            ActionResult::DatabaseDeleted(result) => {
+                return self.database_changed(result, "Database deleted", "Failed to delete database");
+            }
+            ActionResult::DatabaseStopped(result) => {
+                return self.database_changed(result, "Database stopped", "Failed to stop database");
+            }
+            ActionResult::DatabaseRestarted(result) => {
+                return self.database_changed(result, "Database restarted", "Failed to restart database");
+            }
+            ActionResult::ProfileSwitched { name, result: Ok(user), .. } => {
+                // The loop has already adopted the new client
+                if let Err(e) = self.config.use_profile(&name).and_then(|_| save_config(&self.config)) {
+                    self.notifications.error(format!("Failed to save config: {}", e));
+                }
+                self.active_profile = name.clone();
+                self.user = Some(user);
+                self.offline = false;
+                self.projects.clear();
+                self.databases.clear();
+                self.activity.clear();
+                self.selected_project = None;
+                self.storage_stats = None;
+                self.notifications.success(format!("Connected with profile '{}'", name));
+                return vec![Action::FetchProjects];
+            }
+            ActionResult::ProfileSwitched { name, result: Err(e), .. } => {
+                self.notifications.error(format!("Could not connect with '{}': {}", name, e));
             }
         }
-        
-        Ok(())
+
+        Vec::new()
     }
 
-    pub fn next(&mut self) {
-        let len = match self.screen {
-            Screen::Projects => self.projects.len(),
-            Screen::Activity => self.activity.len(),
-            Screen::Storage => self.databases.len(),
-            _ => 0,
-        };
-        
-        if len > 0 {
-            self.selected_index = (self.selected_index + 1) % len;
+    /// Reports builds that were running when last seen and have finished
+    /// since, whichever list the newer status came in with.
+    fn notify_finished<'a>(&mut self, deployments: impl Iterator<Item = &'a Deployment>) {
+        let known: Vec<&Deployment> = self
+            .activity
+            .iter()
+            .chain(self.selected_project.iter().flat_map(|p| p.deployments.iter().flatten()))
+            .chain(self.projects.iter().filter_map(|p| p.deployments.as_ref()?.first()))
+            .collect();
+
+        let mut finished = Vec::new();
+        for deployment in deployments {
+            let was_building = known
+                .iter()
+                .any(|d| d.id == deployment.id && matches!(d.status.as_str(), "building" | "pending"));
+            if was_building && matches!(deployment.status.as_str(), "live" | "failed") {
+                finished.push(deployment);
+            }
         }
-    }
 
-    pub fn previous(&mut self) {
-        let len = match self.screen {
-            Screen::Projects => self.projects.len(),
-            Screen::Activity => self.activity.len(),
-            Screen::Storage => self.databases.len(),
-            _ => 0,
-        };
-        
-        if len > 0 {
-            if self.selected_index == 0 {
-                self.selected_index = len - 1;
+        for deployment in finished {
+            let name = self.project_name(&deployment.project_id);
+            if deployment.status == "live" {
+                self.notifications.success(format!("{} is live ({})", name, deployment.short_commit()));
             } else {
-                self.selected_index -= 1;
+                self.notifications.error(format!("Build of {} failed ({})", name, deployment.short_commit()));
             }
         }
     }
 
-    pub fn select_project(&mut self) {
-        if self.selected_index < self.projects.len() {
-            let project_id = self.projects[self.selected_index].id.clone();
-            self.screen = Screen::ProjectDetail(project_id);
-            self.selected_index = 0;
-            self.message.clear();
-        }
+    fn project_name(&self, id: &str) -> String {
+        self.projects
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| id.to_string())
     }
 
-    pub fn select_database(&mut self) {
-        if self.selected_index < self.databases.len() {
-            self.selected_database = Some(self.databases[self.selected_index].clone());
-            self.show_db_credentials = true;
+    fn database_changed(&mut self, result: ApiResult<()>, done: &str, failed: &str) -> Vec<Action> {
+        match result {
+            Ok(()) => {
+                self.notifications.success(done);
+                vec![Action::FetchStorage]
+            }
+            Err(e) => {
+                self.notifications.error(e.describe(failed));
+                Vec::new()
+            }
         }
     }
+}
 
-    pub fn go_back(&mut self) {
-        match &self.screen {
-            Screen::ProjectDetail(_) | Screen::ProjectSettings(_) | Screen::Activity | Screen::Settings | Screen::CreateProject | Screen::CreateDatabase | Screen::Deployments | Screen::Network | Screen::Storage | Screen::Docs => {
-                self.screen = Screen::Projects;
-                self.selected_index = 0;
-                self.message.clear();
-                self.show_db_credentials = false;
-                self.db_credentials = None;
-            }
-            Screen::DeploymentLogs(_) => {
-                 // Go back to ProjectDetail if we have selected_project
-                 if let Some(p) = &self.selected_project {
-                     self.screen = Screen::ProjectDetail(p.id.clone());
-                 } else {
-                     self.screen = Screen::Projects;
-                 }
-                 self.message.clear();
-            }
-            _ => {}
+impl Screen {
+    /// Human-readable name, as shown in the breadcrumbs.
+    pub fn title(&self, app: &App) -> String {
+        match self {
+            Screen::Projects => "Projects".to_string(),
+            Screen::CreateProject => "New project".to_string(),
+            Screen::CreateDatabase => "New database".to_string(),
+            Screen::ProjectDetail(id) => app
+                .projects
+                .iter()
+                .chain(&app.selected_project)
+                .find(|p| p.id == *id)
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| id.clone()),
+            Screen::ProjectSettings(_) => "Settings".to_string(),
+            Screen::DeploymentLogs(id) => {
+                format!("Deployment {} › Logs", id.chars().take(8).collect::<String>())
+            }
+            Screen::Deployments => "Deployments".to_string(),
+            Screen::Activity => "Activity".to_string(),
+            Screen::Network => "Network".to_string(),
+            Screen::Storage => "Storage".to_string(),
+            Screen::Docs => "Key reference".to_string(),
+            Screen::Settings => "Settings".to_string(),
         }
     }
 }
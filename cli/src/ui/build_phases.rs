@@ -0,0 +1,327 @@
+use chrono::{DateTime, Local, TimeDelta};
+
+/// The steps of a deployment, in the order the server runs them.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Phase {
+    Clone,
+    Plan,
+    Install,
+    Build,
+    Export,
+    Start,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 6] = [
+        Phase::Clone,
+        Phase::Plan,
+        Phase::Install,
+        Phase::Build,
+        Phase::Export,
+        Phase::Start,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Phase::Clone => "Clone",
+            Phase::Plan => "Plan",
+            Phase::Install => "Install",
+            Phase::Build => "Build",
+            Phase::Export => "Export image",
+            Phase::Start => "Start container",
+        }
+    }
+}
+
+/// A phase that has been reached.
+pub struct Step {
+    pub phase: Phase,
+    /// Line the phase's output starts on.
+    pub line: usize,
+    /// When its first line came in; `None` for stored output.
+    pub started: Option<DateTime<Local>>,
+    pub ended: Option<DateTime<Local>>,
+    pub failed: bool,
+}
+
+impl Step {
+    /// How long the phase took, or has taken so far.
+    pub fn elapsed(&self) -> Option<TimeDelta> {
+        Some(self.ended.unwrap_or_else(Local::now) - self.started?)
+    }
+}
+
+/// Works out which phase a build is in from its output, as it comes in.
+/// The server announces cloning, the nixpacks run and the end of the build
+/// with `>>>` lines; install and build are the image steps that run the
+/// project's commands, and the export is BuildKit's.
+#[derive(Default)]
+pub struct BuildPhases {
+    steps: Vec<Step>,
+    /// The project's own install and build commands, if it sets them.
+    install_command: String,
+    build_command: String,
+    finished: bool,
+}
+
+impl BuildPhases {
+    pub fn set_commands(&mut self, install: &str, build: &str) {
+        if self.install_command != install.trim() || self.build_command != build.trim() {
+            self.install_command = install.trim().to_string();
+            self.build_command = build.trim().to_string();
+        }
+    }
+
+    /// Looks at one complete line of output.
+    pub fn observe(&mut self, number: usize, text: &str, received: Option<DateTime<Local>>) {
+        let Some(phase) = self.detect(text.trim()) else {
+            return;
+        };
+        // Phases only move forward; an install command can show up again
+        // in the build output, for example
+        if self.finished || self.steps.last().is_some_and(|step| step.phase >= phase) {
+            return;
+        }
+        if let Some(last) = self.steps.last_mut() {
+            last.ended = received;
+        }
+        self.steps.push(Step {
+            phase,
+            line: number,
+            started: received,
+            ended: None,
+            failed: false,
+        });
+    }
+
+    /// Closes the last phase once the deployment is live or has failed.
+    pub fn finish(&mut self, status: &str) {
+        if self.finished || !matches!(status, "live" | "failed") {
+            return;
+        }
+        self.finished = true;
+        if let Some(last) = self.steps.last_mut() {
+            last.ended = last.started.map(|_| Local::now());
+            last.failed = status == "failed";
+        }
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn step(&self, phase: Phase) -> Option<&Step> {
+        self.steps.iter().find(|step| step.phase == phase)
+    }
+
+    /// Whether `phase` is over: a later one has started, or the deployment
+    /// is done.
+    pub fn is_done(&self, phase: Phase) -> bool {
+        self.finished || self.steps.last().is_some_and(|step| step.phase > phase)
+    }
+
+    fn detect(&self, text: &str) -> Option<Phase> {
+        if text.starts_with(">>> Cloning repository") {
+            return Some(Phase::Clone);
+        }
+        if text.starts_with(">>> Starting Nixpacks build") {
+            return Some(Phase::Plan);
+        }
+        // Dockerfile projects skip nixpacks and go straight to the build
+        if text.starts_with(">>> Detected Dockerfile runtime") {
+            return Some(Phase::Build);
+        }
+        if text.starts_with(">>> Build successful") {
+            return Some(Phase::Start);
+        }
+        if text.contains("exporting to image") || text.starts_with("Successfully built ") {
+            return Some(Phase::Export);
+        }
+        self.run_step(text)
+    }
+
+    /// Install or build for an image step that runs a command, as in
+    /// `#11 [stage-0 7/11] RUN npm ci` or `Step 7/11 : RUN npm ci`.
+    fn run_step(&self, text: &str) -> Option<Phase> {
+        let is_step = text.starts_with("Step ")
+            || (text.starts_with('#') && text[1..].starts_with(|c: char| c.is_ascii_digit()) && text.contains(" ["));
+        if !is_step {
+            return None;
+        }
+        let (_, command) = text.split_once("RUN ")?;
+        // Cache mounts come before the command
+        let command: Vec<&str> = command.split_whitespace().filter(|w| !w.starts_with("--mount=")).collect();
+        let command = command.join(" ");
+
+        let custom = |configured: &str| !configured.is_empty() && command.contains(configured);
+        if custom(&self.build_command) {
+            return Some(Phase::Build);
+        }
+        if custom(&self.install_command) {
+            return Some(Phase::Install);
+        }
+
+        let lower = command.to_lowercase();
+        const BUILD: &[&str] = &["run build", "pnpm build", "yarn build", "task build", "go build", "cargo build"];
+        const INSTALL: &[&str] = &[
+            "npm ci", "npm install", "npm i ", "pnpm install", "pnpm i ", "yarn install", "bun install",
+            "deno cache", "pip install", "poetry install", "bundle install", "go mod download",
+        ];
+        if BUILD.iter().any(|pattern| lower.contains(pattern)) {
+            Some(Phase::Build)
+        } else if INSTALL.iter().any(|pattern| lower.contains(pattern)) || lower == "yarn" {
+            Some(Phase::Install)
+        } else {
+            None
+        }
+    }
+}
+
+/// `4.2s`, or `3m 05s` from a minute on.
+pub fn format_elapsed(elapsed: TimeDelta) -> String {
+    let millis = elapsed.num_milliseconds().max(0);
+    if millis < 60_000 {
+        format!("{:.1}s", millis as f64 / 1000.0)
+    } else {
+        format!("{}m {:02}s", millis / 60_000, millis / 1000 % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A nixpacks deployment as builder.go logs it, with BuildKit output.
+    const NIXPACKS: &str = "\
+>>> Cloning repository https://github.com/acme/shop...
+Cloning into '.'...
+>>> Checked out commit: 4f2c9e1a7b
+
+>>> Starting Nixpacks build for shop...
+
+╔══════════════════════════════ Nixpacks v1.29.1 ══════════════════════════════╗
+║ setup      │ nodejs_20                                                       ║
+║────────────────────────────────────────────────────────────────────────────────║
+║ install    │ npm ci --legacy-peer-deps || npm install --legacy-peer-deps     ║
+║────────────────────────────────────────────────────────────────────────────────║
+║ build      │ npm run build                                                   ║
+║────────────────────────────────────────────────────────────────────────────────║
+║ start      │ npm run start                                                   ║
+╚════════════════════════════════════════════════════════════════════════════════╝
+
+#0 building with \"default\" instance using docker driver
+#5 [stage-0  2/10] WORKDIR /app/
+#8 [stage-0  5/10] COPY . /app/.
+#9 [stage-0  6/10] RUN --mount=type=cache,id=s/abc-/root/npm,target=/root/.npm npm ci --legacy-peer-deps || npm install --legacy-peer-deps
+#9 4.210 added 312 packages in 4s
+#9 DONE 5.1s
+#11 [stage-0  8/10] RUN --mount=type=cache,id=s/abc-next/cache,target=/app/.next/cache npm run build
+#11 0.512 > shop@1.0.0 build
+#11 0.512 > next build
+#11 DONE 21.4s
+#13 exporting to image
+#13 exporting layers 1.2s done
+#13 writing image sha256:0c1d done
+#13 naming to docker.io/library/shop done
+#13 DONE 1.3s
+
+>>> Build successful!
+";
+
+    fn observe_all(phases: &mut BuildPhases, logs: &str) {
+        for (number, line) in logs.lines().enumerate() {
+            phases.observe(number, line, None);
+        }
+    }
+
+    fn detected(phases: &BuildPhases) -> Vec<(Phase, usize)> {
+        phases.steps().iter().map(|step| (step.phase, step.line)).collect()
+    }
+
+    #[test]
+    fn nixpacks_build() {
+        let mut phases = BuildPhases::default();
+        observe_all(&mut phases, NIXPACKS);
+        assert_eq!(detected(&phases), [
+            (Phase::Clone, 0),
+            (Phase::Plan, 4),
+            (Phase::Install, 19),
+            (Phase::Build, 22),
+            (Phase::Export, 26),
+            (Phase::Start, 32),
+        ]);
+    }
+
+    #[test]
+    fn dockerfile_build() {
+        let logs = "\
+>>> Cloning repository https://github.com/acme/api...
+>>> Checked out commit: 9e8d7c6
+
+>>> Detected Dockerfile runtime. Building Docker image api...
+Step 1/6 : FROM golang:1.22
+Step 4/6 : RUN go mod download
+Step 5/6 : RUN go build -o /server .
+Successfully built 3b1f0a9c2d4e
+Successfully tagged api:latest
+
+>>> Build successful!
+";
+        let mut phases = BuildPhases::default();
+        observe_all(&mut phases, logs);
+        // The Dockerfile's install step comes after the build started, and
+        // phases only move forward
+        assert_eq!(detected(&phases), [
+            (Phase::Clone, 0),
+            (Phase::Build, 3),
+            (Phase::Export, 7),
+            (Phase::Start, 10),
+        ]);
+    }
+
+    #[test]
+    fn custom_commands() {
+        let logs = "\
+>>> Starting Nixpacks build for site...
+#9 [stage-0  6/10] RUN make deps
+#11 [stage-0  8/10] RUN make dist
+";
+        let mut phases = BuildPhases::default();
+        observe_all(&mut phases, logs);
+        assert_eq!(detected(&phases), [(Phase::Plan, 0)]);
+
+        let mut phases = BuildPhases::default();
+        phases.set_commands(" make deps ", "make dist");
+        observe_all(&mut phases, logs);
+        assert_eq!(detected(&phases), [(Phase::Plan, 0), (Phase::Install, 1), (Phase::Build, 2)]);
+    }
+
+    #[test]
+    fn failure_ends_the_last_phase() {
+        let cut = NIXPACKS.lines().take(23).collect::<Vec<_>>().join("\n");
+        let mut phases = BuildPhases::default();
+        for (number, line) in cut.lines().enumerate() {
+            phases.observe(number, line, Some(Local::now()));
+        }
+        assert!(!phases.is_done(Phase::Build));
+        assert!(phases.is_done(Phase::Install));
+
+        phases.finish("building");
+        assert!(!phases.is_done(Phase::Build));
+        phases.finish("failed");
+        let build = phases.step(Phase::Build).unwrap();
+        assert!(build.failed && build.ended.is_some());
+        assert!(phases.is_done(Phase::Build));
+
+        // Nothing moves once the deployment is over
+        phases.observe(40, ">>> Build successful!", None);
+        assert!(phases.step(Phase::Start).is_none());
+    }
+
+    #[test]
+    fn elapsed_format() {
+        assert_eq!(format_elapsed(TimeDelta::milliseconds(4_210)), "4.2s");
+        assert_eq!(format_elapsed(TimeDelta::seconds(185)), "3m 05s");
+        assert_eq!(format_elapsed(TimeDelta::seconds(-1)), "0.0s");
+    }
+}
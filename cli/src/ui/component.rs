@@ -0,0 +1,177 @@
+use crate::api::ApiClient;
+use crate::ui::action::{Action, ActionResult, Tasks};
+use crate::ui::app::{App, Screen};
+use crate::ui::confirm::{dialog_for, Confirmation};
+use crate::ui::keymap::{Keymap, Scope};
+use anyhow::Result;
+use crossterm::event::{Event, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// A key and what it does on the current screen.
+pub struct KeyHint {
+    pub key: String,
+    pub description: &'static str,
+    /// What clicking the hint presses, if it stands for a single key.
+    pub press: Option<KeyEvent>,
+}
+
+impl KeyHint {
+    pub fn new(key: impl Into<String>, description: &'static str) -> Self {
+        Self { key: key.into(), description, press: None }
+    }
+
+    pub fn clickable(mut self, press: KeyEvent) -> Self {
+        self.press = Some(press);
+        self
+    }
+}
+
+/// Where to go after a component handled an event or a result.
+pub enum Transition {
+    Stay,
+    Open(Screen),
+    Back,
+    Forward,
+    Quit,
+}
+
+/// What a component can reach while handling input.
+pub struct Ctx<'a> {
+    pub app: &'a mut App,
+    tasks: &'a mut Tasks,
+}
+
+impl<'a> Ctx<'a> {
+    pub fn new(app: &'a mut App, tasks: &'a mut Tasks) -> Self {
+        Self { app, tasks }
+    }
+
+    pub fn dispatch(&mut self, action: Action) {
+        self.tasks.dispatch(self.app, action);
+    }
+
+    /// Like `dispatch`, but asks first when the action stops, restarts or
+    /// deletes something.
+    pub fn confirm(&mut self, action: Action) {
+        match dialog_for(&action, self.app) {
+            Some(dialog) => self.app.confirm = Some(Confirmation { dialog, action }),
+            None => self.dispatch(action),
+        }
+    }
+
+    pub fn client(&self) -> &ApiClient {
+        self.tasks.client()
+    }
+}
+
+/// A screen of the TUI. Each one owns its UI state; data shared between
+/// screens (projects, activity, databases...) stays in `App`.
+pub trait Component {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App);
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition>;
+
+    /// Which key bindings apply on this screen.
+    fn scope(&self) -> Scope;
+
+    /// Keys this screen responds to in its current state.
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint>;
+
+    fn on_enter(&mut self, _ctx: &mut Ctx) {}
+
+    fn on_leave(&mut self, _ctx: &mut Ctx) {}
+
+    /// Called with every finished action before `App::apply` sees it.
+    fn on_result(&mut self, _result: &ActionResult, _app: &mut App) -> Transition {
+        Transition::Stay
+    }
+
+    /// Called once per frame, before drawing.
+    fn tick(&mut self, _ctx: &mut Ctx) {}
+
+    /// Highlights the item with this id, if the screen lists it.
+    fn focus(&mut self, _id: &str, _app: &App) {}
+
+    /// True while the screen takes typed text, so single-letter global
+    /// keys like `q` and `:` are left to it.
+    fn is_editing(&self) -> bool {
+        false
+    }
+}
+
+const SEPARATOR: &str = " | ";
+
+/// Where each hint goes inside the footer, moving on to the next row when
+/// a hint doesn't fit on the current one.
+fn hint_areas(hints: &[KeyHint], inner: Rect) -> Vec<Rect> {
+    let separator = SEPARATOR.len() as u16;
+    let (mut x, mut y) = (0, 0);
+    let mut areas = Vec::new();
+    for hint in hints {
+        let width = (hint.key.chars().count() + 1 + hint.description.chars().count()) as u16;
+        if x > 0 && x + separator + width > inner.width {
+            x = 0;
+            y += 1;
+        } else if x > 0 {
+            x += separator;
+        }
+        areas.push(Rect {
+            x: inner.x + x,
+            y: inner.y + y,
+            width: width.min(inner.width),
+            height: 1,
+        });
+        x += width;
+    }
+    areas
+}
+
+/// Rows the footer needs at this width, borders included.
+pub fn footer_height(hints: &[KeyHint], width: u16) -> u16 {
+    let inner = Rect::new(0, 0, width.saturating_sub(2), u16::MAX);
+    let rows = hint_areas(hints, inner).last().map_or(1, |area| area.y + 1);
+    rows + 2
+}
+
+/// Draws the hints and returns where each one ended up, so clicks on them
+/// can be mapped back to their keys.
+pub fn render_footer(f: &mut Frame, area: Rect, hints: &[KeyHint]) -> Vec<Rect> {
+    let block = Block::default().borders(Borders::ALL);
+    let areas = hint_areas(hints, block.inner(area));
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (hint, hint_area) in hints.iter().zip(&areas) {
+        let row = (hint_area.y - area.y) as usize - 1;
+        if row == lines.len() {
+            lines.push(Line::default());
+        } else {
+            lines[row].push_span(Span::raw(SEPARATOR));
+        }
+        lines[row].push_span(Span::styled(hint.key.as_str(), Style::default().fg(Color::Yellow)));
+        lines[row].push_span(Span::raw(format!(" {}", hint.description)));
+    }
+
+    let footer = Paragraph::new(lines)
+        .block(block)
+        .style(Style::default().fg(Color::DarkGray));
+    f.render_widget(footer, area);
+    areas
+}
+
+pub fn select_next(index: usize, len: usize) -> usize {
+    if len == 0 { 0 } else { (index + 1) % len }
+}
+
+pub fn select_previous(index: usize, len: usize) -> usize {
+    match index {
+        _ if len == 0 => 0,
+        0 => len - 1,
+        _ => index - 1,
+    }
+}
@@ -0,0 +1,185 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use tui_input::{Input, InputRequest};
+
+use crate::ui::action::Action;
+use crate::ui::app::App;
+
+pub enum Answer {
+    Pending,
+    Yes,
+    No,
+}
+
+/// A modal yes/no question. Irreversible actions can require typing a name
+/// instead, so a stray key press can't confirm them.
+pub struct ConfirmDialog {
+    title: String,
+    message: String,
+    expected: Option<String>,
+    input: Input,
+    mismatch: bool,
+}
+
+impl ConfirmDialog {
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            expected: None,
+            input: Input::default(),
+            mismatch: false,
+        }
+    }
+
+    /// Only accept once `name` has been typed.
+    pub fn type_to_confirm(mut self, name: impl Into<String>) -> Self {
+        self.expected = Some(name.into());
+        self
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Answer {
+        let Some(expected) = &self.expected else {
+            return match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => Answer::Yes,
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => Answer::No,
+                _ => Answer::Pending,
+            };
+        };
+
+        match key.code {
+            KeyCode::Esc => return Answer::No,
+            KeyCode::Enter if self.input.value() == expected => return Answer::Yes,
+            KeyCode::Enter => self.mismatch = true,
+            KeyCode::Backspace => {
+                self.input.handle(InputRequest::DeletePrevChar);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input.handle(InputRequest::DeleteLine);
+            }
+            KeyCode::Char(c) => {
+                self.input.handle(InputRequest::InsertChar(c));
+                self.mismatch = false;
+            }
+            _ => {}
+        }
+        Answer::Pending
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let width = area.width.clamp(20, 64);
+        let height = if self.expected.is_some() { 8 } else { 6 };
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 3,
+            width,
+            height: height.min(area.height),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title.as_str())
+            .border_style(Style::default().fg(Color::Red));
+        let inner = block.inner(popup);
+        f.render_widget(Clear, popup);
+        f.render_widget(block, popup);
+
+        let rows = if self.expected.is_some() { 3 } else { 1 };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(rows)])
+            .split(inner);
+
+        let message = Paragraph::new(self.message.as_str()).wrap(Wrap { trim: true });
+        f.render_widget(message, chunks[0]);
+
+        let Some(expected) = &self.expected else {
+            let keys = Line::from(vec![
+                Span::styled("y", Style::default().fg(Color::Yellow)),
+                Span::raw(" Yes | "),
+                Span::styled("n/Esc", Style::default().fg(Color::Yellow)),
+                Span::raw(" No"),
+            ]);
+            f.render_widget(Paragraph::new(keys), chunks[1]);
+            return;
+        };
+
+        let hint = if self.mismatch {
+            Line::from(Span::styled("Doesn't match", Style::default().fg(Color::Red)))
+        } else {
+            Line::from(Span::styled("Enter: Confirm | Esc: Cancel", Style::default().fg(Color::DarkGray)))
+        };
+        let lines = vec![
+            Line::from(vec![
+                Span::raw("Type "),
+                Span::styled(expected.as_str(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to confirm:"),
+            ]),
+            Line::from(vec![
+                Span::styled("> ", Style::default().fg(Color::Yellow)),
+                Span::raw(self.input.value()),
+            ]),
+            hint,
+        ];
+        f.render_widget(Paragraph::new(lines), chunks[1]);
+        f.set_cursor_position((
+            chunks[1].x + 2 + self.input.visual_cursor() as u16,
+            chunks[1].y + 1,
+        ));
+    }
+}
+
+/// A server action waiting for the user to confirm it.
+pub struct Confirmation {
+    pub dialog: ConfirmDialog,
+    pub action: Action,
+}
+
+/// The question to ask before running `action`, if it stops, restarts or
+/// deletes something.
+pub fn dialog_for(action: &Action, app: &App) -> Option<ConfirmDialog> {
+    let project_name = |id: &str| {
+        app.projects
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let database_name = |id: u32| {
+        app.databases
+            .iter()
+            .find(|db| db.id == id)
+            .map(|db| db.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    let dialog = match action {
+        Action::StopProject(id) => ConfirmDialog::new(
+            "Stop project",
+            format!("Stop {}? It stays offline until the next deploy.", project_name(id)),
+        ),
+        Action::StopDatabase(id) => ConfirmDialog::new(
+            "Stop database",
+            format!("Stop {}? Apps using it lose their connection.", database_name(*id)),
+        ),
+        Action::RestartDatabase(id) => ConfirmDialog::new(
+            "Restart database",
+            format!("Restart {}? Open connections are dropped.", database_name(*id)),
+        ),
+        Action::DeleteDatabase(id) => {
+            let name = database_name(*id);
+            ConfirmDialog::new(
+                "Delete database",
+                format!("Delete {} and all of its data? This can't be undone.", name),
+            )
+            .type_to_confirm(name)
+        }
+        _ => return None,
+    };
+    Some(dialog)
+}
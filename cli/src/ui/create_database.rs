@@ -0,0 +1,188 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+use crate::ui::action::{Action, ActionResult};
+use crate::ui::app::App;
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::mouse::{contains, is_left_click};
+
+const DB_TYPES: [&str; 2] = ["sqlite", "mongodb"];
+
+pub struct CreateDatabaseView {
+    name: String,
+    db_type: String,
+    focused_field: usize,
+    name_area: Rect,
+    type_area: Rect,
+}
+
+impl CreateDatabaseView {
+    pub fn new() -> Self {
+        Self {
+            name: String::new(),
+            db_type: "sqlite".to_string(),
+            focused_field: 0,
+            name_area: Rect::default(),
+            type_area: Rect::default(),
+        }
+    }
+
+    fn toggle_type(&mut self) {
+        self.db_type = if self.db_type == "sqlite" {
+            "mongodb".to_string()
+        } else {
+            "sqlite".to_string()
+        };
+    }
+}
+
+impl Component for CreateDatabaseView {
+    fn render(&mut self, f: &mut Frame, area: Rect, _app: &App) {
+        render(f, area, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                if !is_left_click(mouse) {
+                    return Ok(Transition::Stay);
+                }
+                if contains(self.name_area, mouse) {
+                    self.focused_field = 0;
+                } else if contains(self.type_area, mouse) {
+                    self.focused_field = 1;
+                    // Rows inside the border, in DB_TYPES order
+                    let row = mouse.row.saturating_sub(self.type_area.y + 1) as usize;
+                    if let Some(db_type) = DB_TYPES.get(row) {
+                        self.db_type = db_type.to_string();
+                    }
+                }
+                return Ok(Transition::Stay);
+            }
+            _ => return Ok(Transition::Stay),
+        };
+
+        match ctx.app.keys.action(Scope::Form, key) {
+            Some(KeyAction::Cancel) => return Ok(Transition::Back),
+            Some(KeyAction::NextField | KeyAction::PreviousField) => {
+                self.focused_field = (self.focused_field + 1) % 2;
+            }
+            Some(KeyAction::Submit) => {
+                if !self.name.is_empty() {
+                    ctx.dispatch(Action::CreateDatabase {
+                        name: self.name.clone(),
+                        db_type: self.db_type.clone(),
+                    });
+                }
+            }
+            _ => match key.code {
+                KeyCode::Char(c) if self.focused_field == 0 => self.name.push(c),
+                KeyCode::Backspace => {
+                    if self.focused_field == 0 {
+                        self.name.pop();
+                    } else {
+                        return Ok(Transition::Back);
+                    }
+                }
+                KeyCode::Up | KeyCode::Down if self.focused_field == 1 => self.toggle_type(),
+                _ => {}
+            },
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Form
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        let mut hints = keys.hints(Scope::Form, &[KeyAction::NextField]);
+        hints.push(KeyHint::new("↑↓", "Select Type"));
+        hints.extend(keys.hints(Scope::Form, &[KeyAction::Submit, KeyAction::Cancel]));
+        hints
+    }
+
+    fn is_editing(&self) -> bool {
+        self.focused_field == 0
+    }
+
+    fn on_result(&mut self, result: &ActionResult, _app: &mut App) -> Transition {
+        match result {
+            ActionResult::DatabaseCreated(Ok(())) => Transition::Back,
+            _ => Transition::Stay,
+        }
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, state: &mut CreateDatabaseView) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    // Header
+    let header = Paragraph::new("Create New Database")
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    f.render_widget(header, chunks[0]);
+
+    // Form
+    let form_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(5),
+            Constraint::Min(0),
+        ])
+        .margin(1)
+        .split(chunks[1]);
+
+    // Name input
+    let name_style = if state.focused_field == 0 {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let name_input = Paragraph::new(state.name.as_str())
+        .block(Block::default().borders(Borders::ALL).title("Name").border_style(name_style));
+    f.render_widget(name_input, form_chunks[0]);
+
+    // Type selection
+    let type_style = if state.focused_field == 1 {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    
+    let type_list: Vec<Line> = DB_TYPES.iter().map(|&t| {
+        if t == state.db_type {
+            Line::from(vec![
+                Span::styled("▸ ", Style::default().fg(Color::Green)),
+                Span::styled(t, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("  "),
+                Span::raw(t),
+            ])
+        }
+    }).collect();
+
+    let type_select = Paragraph::new(type_list)
+        .block(Block::default().borders(Borders::ALL).title("Type").border_style(type_style));
+    f.render_widget(type_select, form_chunks[1]);
+
+    state.name_area = form_chunks[0];
+    state.type_area = form_chunks[1];
+}
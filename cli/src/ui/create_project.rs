@@ -1,13 +1,20 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
-use tui_input::Input;
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+use tui_input::{Input, InputRequest};
+use crate::models::CreateProjectRequest;
+use crate::ui::action::{Action, ActionResult};
+use crate::ui::app::App;
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::mouse::{contains, is_left_click};
 
-pub struct CreateProjectState {
+pub struct CreateProjectView {
     pub name: Input,
     pub repo_url: Input,
     pub build_command: Input,
@@ -15,9 +22,11 @@ pub struct CreateProjectState {
     pub install_command: Input,
     pub focused_field: usize,
     pub error: Option<String>,
+    /// Where each field was drawn, in `focused_field` order.
+    field_areas: [Rect; 5],
 }
 
-impl CreateProjectState {
+impl CreateProjectView {
     pub fn new() -> Self {
         Self {
             name: Input::default(),
@@ -27,6 +36,7 @@ impl CreateProjectState {
             install_command: Input::default(),
             focused_field: 0,
             error: None,
+            field_areas: [Rect::default(); 5],
         }
     }
 
@@ -41,9 +51,117 @@ impl CreateProjectState {
             self.focused_field -= 1;
         }
     }
+
+    fn focused_input(&mut self) -> Option<&mut Input> {
+        match self.focused_field {
+            0 => Some(&mut self.name),
+            1 => Some(&mut self.repo_url),
+            2 => Some(&mut self.install_command),
+            3 => Some(&mut self.build_command),
+            4 => Some(&mut self.start_command),
+            _ => None,
+        }
+    }
+
+    fn request(&self) -> Result<CreateProjectRequest, String> {
+        let name = self.name.value().trim().to_string();
+        let repo = self.repo_url.value().trim().to_string();
+
+        if name.is_empty() || repo.is_empty() {
+            return Err("Name and Repo URL are required".to_string());
+        }
+
+        let optional = |input: &Input| {
+            let value = input.value().trim();
+            if value.is_empty() { None } else { Some(value.to_string()) }
+        };
+
+        Ok(CreateProjectRequest {
+            name,
+            repo,
+            port: Some(3000), // Default port
+            git_token: None,
+            env_vars: None,
+            build_command: optional(&self.build_command),
+            start_command: optional(&self.start_command),
+            install_command: optional(&self.install_command),
+            runtime: None,
+        })
+    }
+}
+
+impl Component for CreateProjectView {
+    fn render(&mut self, f: &mut Frame, area: Rect, _app: &App) {
+        render(f, area, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                if is_left_click(mouse)
+                    && let Some(field) = self.field_areas.iter().position(|area| contains(*area, mouse))
+                {
+                    self.focused_field = field;
+                }
+                return Ok(Transition::Stay);
+            }
+            _ => return Ok(Transition::Stay),
+        };
+
+        match ctx.app.keys.action(Scope::Form, key) {
+            Some(KeyAction::Cancel) => return Ok(Transition::Back),
+            Some(KeyAction::NextField) => self.next_field(),
+            Some(KeyAction::PreviousField) => self.previous_field(),
+            Some(KeyAction::Submit) => match self.request() {
+                Ok(request) => {
+                    self.error = None;
+                    ctx.dispatch(Action::CreateProject(request));
+                }
+                Err(e) => self.error = Some(e),
+            },
+            _ => match key.code {
+                KeyCode::Char(c) => {
+                    if let Some(input) = self.focused_input() {
+                        input.handle(InputRequest::InsertChar(c));
+                    }
+                }
+                KeyCode::Backspace => {
+                    if let Some(input) = self.focused_input() {
+                        input.handle(InputRequest::DeletePrevChar);
+                    }
+                }
+                _ => {}
+            },
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Form
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        keys.hints(Scope::Form, &[KeyAction::NextField, KeyAction::Submit, KeyAction::Cancel])
+    }
+
+    fn is_editing(&self) -> bool {
+        true
+    }
+
+    fn on_result(&mut self, result: &ActionResult, _app: &mut App) -> Transition {
+        match result {
+            ActionResult::ProjectCreated(Ok(_)) => Transition::Back,
+            ActionResult::ProjectCreated(Err(e)) => {
+                self.error = Some(e.describe("Failed to create project"));
+                Transition::Stay
+            }
+            _ => Transition::Stay,
+        }
+    }
 }
 
-pub fn render(f: &mut Frame, area: Rect, state: &CreateProjectState) {
+fn render(f: &mut Frame, area: Rect, state: &mut CreateProjectView) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -53,7 +171,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &CreateProjectState) {
             Constraint::Length(3), // Install
             Constraint::Length(3), // Build
             Constraint::Length(3), // Start
-            Constraint::Min(0),    // Error/Help
+            Constraint::Min(0),    // Error
         ])
         .split(area);
 
@@ -90,6 +208,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &CreateProjectState) {
         let widget = Paragraph::new(input.value()).block(block);
         f.render_widget(widget, chunks[i + 1]);
     }
+    state.field_areas.copy_from_slice(&chunks[1..6]);
 
     if let Some(error) = &state.error {
         let error_widget = Paragraph::new(error.as_str())
@@ -97,10 +216,5 @@ pub fn render(f: &mut Frame, area: Rect, state: &CreateProjectState) {
             .block(Block::default().borders(Borders::ALL).title("Error"))
             .wrap(Wrap { trim: true });
         f.render_widget(error_widget, chunks[6]);
-    } else {
-        let help = Paragraph::new("Tab: Next Field | Enter: Create | Esc: Cancel")
-            .style(Style::default().fg(Color::DarkGray))
-            .alignment(Alignment::Center);
-        f.render_widget(help, chunks[6]);
     }
 }
@@ -1,32 +1,324 @@
+use crate::api::{LogEvent, StreamState};
+use crate::models::{Deployment, Project};
+use crate::ui::action::Action;
 use crate::ui::app::App;
+use crate::ui::build_phases::{format_elapsed, BuildPhases, Phase};
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::failure_analysis::FailureSummary;
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::log_export::{self, ExportAnswer, ExportDialog, Format};
+use crate::ui::log_viewer::{lines, LogViewer, Section};
+use anyhow::Result;
+use chrono::Local;
+use crossterm::event::Event;
+use std::path::Path;
+use std::time::{Duration, Instant};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
-pub fn render(f: &mut Frame, area: Rect, app: &App, deployment_id: &str) {
+pub struct DeploymentLogsView {
+    deployment_id: String,
+    logs: LogViewer,
+    /// Output has come in over the stream, rather than only the logs
+    /// stored with the deployment.
+    live: bool,
+    /// The stored logs shown so far.
+    stored: String,
+    stream_state: Option<StreamState>,
+    stream: Option<(JoinHandle<()>, mpsc::UnboundedReceiver<LogEvent>)>,
+    export: Option<ExportDialog>,
+    phases: BuildPhases,
+    /// Lines looked at for phase changes so far.
+    observed: usize,
+    /// When the deployment's status was last asked for.
+    last_poll: Option<Instant>,
+    summary: FailureSummary,
+    /// The failure summary is shown above the log; `a` hides it.
+    show_summary: bool,
+}
+
+/// How often the status of a deployment that is still building is fetched,
+/// since the stream doesn't say when it's done.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+impl DeploymentLogsView {
+    pub fn new(deployment_id: String) -> Self {
+        Self {
+            deployment_id,
+            logs: LogViewer::new(),
+            live: false,
+            stored: String::new(),
+            stream_state: None,
+            stream: None,
+            export: None,
+            phases: BuildPhases::default(),
+            observed: 0,
+            last_poll: None,
+            summary: FailureSummary::default(),
+            show_summary: true,
+        }
+    }
+
+    /// Takes in what the stream sent since the last tick.
+    fn drain_stream(&mut self) {
+        let Some((_, rx)) = &mut self.stream else {
+            return;
+        };
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                LogEvent::Chunk(chunk) => {
+                    if !self.live {
+                        self.live = true;
+                        self.logs.end_line();
+                    }
+                    self.logs.push(&chunk, Some(Local::now()));
+                }
+                LogEvent::State(state) => self.stream_state = Some(state),
+            }
+        }
+    }
+
+    /// Looks for phase changes in the lines that came in since the last
+    /// tick, and marks where each phase starts in the log.
+    fn track_phases(&mut self, ctx: &mut Ctx) {
+        let Some(dep) = find_deployment(ctx.app, &self.deployment_id) else {
+            return;
+        };
+        let buffer = self.logs.buffer();
+        if self.observed < buffer.complete_end() {
+            if let Some(project) = find_project(ctx.app, &dep.project_id) {
+                self.phases.set_commands(&project.install_command, &project.build_command);
+            }
+            for number in self.observed.max(buffer.first())..buffer.complete_end() {
+                if let Some(line) = buffer.get(number) {
+                    self.phases.observe(number, &line.content.text, line.received);
+                }
+            }
+            self.observed = buffer.complete_end();
+        }
+        self.phases.finish(&dep.status);
+        if self.phases.steps().is_empty() {
+            return;
+        }
+
+        let sections = self
+            .phases
+            .steps()
+            .iter()
+            .map(|step| Section {
+                start: step.line,
+                summary: match step.elapsed() {
+                    Some(elapsed) => format!("{} {}", step.phase.label(), format_elapsed(elapsed)),
+                    None => step.phase.label().to_string(),
+                },
+            })
+            .collect();
+        self.logs.set_sections(sections);
+    }
+
+    /// Asks for the deployment again every few seconds while it builds, to
+    /// learn when it's done and pick up its stored log.
+    fn poll_status(&mut self, ctx: &mut Ctx) {
+        if let Some(dep) = find_deployment(ctx.app, &self.deployment_id)
+            && dep.status == "building"
+            && !ctx.app.offline
+            && self.last_poll.is_none_or(|at| at.elapsed() >= POLL_INTERVAL)
+        {
+            self.last_poll = Some(Instant::now());
+            let project_id = dep.project_id.clone();
+            ctx.dispatch(Action::FetchProjectDetail(project_id));
+        }
+    }
+
+    /// Writes the log to `path` and reports how that went.
+    fn write(&self, path: &Path, format: Format, ctx: &mut Ctx) {
+        match log_export::write(path, self.logs.buffer(), format) {
+            Ok(count) => ctx.app.notifications.success(format!("Saved {} to {}", lines(count), path.display())),
+            Err(e) => ctx.app.notifications.error(format!("{:#}", e)),
+        }
+    }
+}
+
+impl Component for DeploymentLogsView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        render(f, area, app, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                self.logs.handle_mouse(mouse);
+                return Ok(Transition::Stay);
+            }
+            _ => return Ok(Transition::Stay),
+        };
+
+        if let Some(dialog) = &mut self.export {
+            match dialog.handle_key(key) {
+                ExportAnswer::Pending => {}
+                ExportAnswer::Cancel => self.export = None,
+                ExportAnswer::Write(path, format) => {
+                    self.export = None;
+                    self.write(&path, format, ctx);
+                }
+            }
+            return Ok(Transition::Stay);
+        }
+
+        if self.logs.handle_key(key, &ctx.app.keys) {
+            return Ok(Transition::Stay);
+        }
+        match ctx.app.keys.action(Scope::Logs, key) {
+            Some(KeyAction::Back) => return Ok(Transition::Back),
+            Some(KeyAction::Copy) => {
+                let (count, text) = self.logs.copy_text();
+                if count == 0 {
+                    ctx.app.notifications.info("Nothing to copy yet");
+                } else if let Err(e) = log_export::copy_to_clipboard(&text) {
+                    ctx.app.notifications.error(format!("Failed to copy: {}", e));
+                } else {
+                    ctx.app.notifications.success(format!("Copied {} to the clipboard", lines(count)));
+                }
+            }
+            Some(KeyAction::Save) => {
+                let path = log_export::default_path(&self.deployment_id, Format::Plain);
+                self.write(&path, Format::Plain, ctx);
+            }
+            Some(KeyAction::Export) => self.export = Some(ExportDialog::new(&self.deployment_id)),
+            Some(KeyAction::FailureSummary) if self.summary.is_shown() => self.show_summary = !self.show_summary,
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn is_editing(&self) -> bool {
+        self.logs.is_editing() || self.export.is_some()
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Logs
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        if self.export.is_some() {
+            return vec![
+                KeyHint::new("Enter", "Export"),
+                KeyHint::new("Tab", "Format"),
+                KeyHint::new("Esc", "Cancel"),
+            ];
+        }
+        if self.logs.is_editing() {
+            return self.logs.keymap(keys);
+        }
+        let mut hints = keys.hints(Scope::Logs, &[KeyAction::Back]);
+        hints.extend(self.logs.keymap(keys));
+        if self.summary.is_shown() {
+            hints.extend(keys.hints(Scope::Logs, &[KeyAction::FailureSummary]));
+        }
+        hints.extend(keys.hints(Scope::Logs, &[KeyAction::Copy, KeyAction::Save, KeyAction::Export, KeyAction::Quit]));
+        hints
+    }
+
+    fn on_enter(&mut self, ctx: &mut Ctx) {
+        // Coming back to a view whose stream is still running
+        if ctx.app.offline || self.stream.as_ref().is_some_and(|(task, _)| !task.is_finished()) {
+            return;
+        }
+
+        let client = ctx.client().clone();
+        let deployment_id = self.deployment_id.clone();
+        let (tx, rx) = mpsc::unbounded_channel();
+        // Failures are reported through the stream state
+        let task = tokio::spawn(async move {
+            let _ = client.stream_logs(&deployment_id, tx).await;
+        });
+        self.stream = Some((task, rx));
+    }
+
+    /// The view stays in the history, so the stream is closed here rather
+    /// than when it's dropped. Output already received is kept.
+    fn on_leave(&mut self, _ctx: &mut Ctx) {
+        self.drain_stream();
+        if let Some((task, _)) = self.stream.take() {
+            task.abort();
+        }
+    }
+
+    fn tick(&mut self, ctx: &mut Ctx) {
+        // Until the stream sends something, show the logs stored with the
+        // deployment as they are refreshed; live output goes after them
+        if !self.live
+            && let Some(dep) = find_deployment(ctx.app, &self.deployment_id)
+            && dep.logs != self.stored
+        {
+            // The server replaces the "Starting build..." placeholder with
+            // the full log when the build ends, so start over unless it grew
+            let new = match dep.logs.strip_prefix(self.stored.as_str()) {
+                Some(new) => new,
+                None => {
+                    self.logs.clear();
+                    self.phases = BuildPhases::default();
+                    self.observed = 0;
+                    &dep.logs
+                }
+            };
+            self.logs.push(new, None);
+            self.stored = dep.logs.clone();
+        }
+
+        self.drain_stream();
+        self.track_phases(ctx);
+        self.poll_status(ctx);
+        self.summary.update(&ctx.app.failure_rules, find_deployment(ctx.app, &self.deployment_id));
+    }
+}
+
+// Views left for another screen have closed their stream already; this
+// covers the current one when the app quits
+impl Drop for DeploymentLogsView {
+    fn drop(&mut self) {
+        if let Some((task, _)) = self.stream.take() {
+            task.abort();
+        }
+    }
+}
+
+fn find_deployment<'a>(app: &'a App, deployment_id: &str) -> Option<&'a Deployment> {
+    app.selected_project
+        .iter()
+        .flat_map(|project| project.deployments.iter().flatten())
+        .chain(&app.activity)
+        .find(|d| d.id == deployment_id)
+}
+
+fn find_project<'a>(app: &'a App, project_id: &str) -> Option<&'a Project> {
+    app.selected_project.iter().chain(&app.projects).find(|p| p.id == project_id)
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App, view: &mut DeploymentLogsView) {
+    let deployment_id = view.deployment_id.as_str();
+    let steps = view.phases.steps();
+    let summary_height = if view.show_summary { view.summary.height() } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Logs
-            Constraint::Length(3), // Footer
+            // Header, with the build steps under it once there are any
+            Constraint::Length(if steps.is_empty() { 3 } else { 4 }),
+            Constraint::Length(summary_height), // Failure summary
+            Constraint::Min(0),                 // Logs
         ])
         .split(area);
 
     // Header with deployment info
-    let deployment = if let Some(project) = &app.selected_project {
-        if let Some(deployments) = &project.deployments {
-            deployments.iter().find(|d| d.id == deployment_id)
-        } else {
-            None
-        }
-    } else {
-        app.activity.iter().find(|d| d.id == deployment_id)
-    };
+    let deployment = find_deployment(app, deployment_id);
 
     let header_text = if let Some(dep) = deployment {
         let commit_short = if dep.commit.len() > 7 {
@@ -41,51 +333,66 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, deployment_id: &str) {
         format!("Deployment: {}", deployment_id)
     };
 
-    let header = Paragraph::new(header_text)
+    // Stream state, shown on the right of the header
+    let (state_label, state_color) = match view.stream_state {
+        None | Some(StreamState::Connecting) => ("◌ connecting", Color::Yellow),
+        Some(StreamState::Live) => ("● live", Color::Green),
+        Some(StreamState::Reconnecting) => ("◌ reconnecting", Color::Yellow),
+        Some(StreamState::Closed) => ("○ closed", Color::Red),
+    };
+    let state_label = if app.offline { "○ offline" } else { state_label };
+
+    let mut lines = vec![Line::from(vec![
+        Span::raw(header_text),
+        Span::raw(" | "),
+        Span::styled(state_label, Style::default().fg(state_color)),
+    ])];
+    if !steps.is_empty() {
+        lines.push(step_tracker(&view.phases));
+    }
+    let header = Paragraph::new(lines)
         .block(Block::default().borders(Borders::ALL).title("Live Logs"))
         .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
     f.render_widget(header, chunks[0]);
 
-    // Logs content - use live_logs if available, otherwise fall back to stored logs
-    let logs_content = if !app.live_logs.is_empty() {
-        app.live_logs.clone()
-    } else if let Some(dep) = deployment {
-        if dep.logs.is_empty() {
-            "Connecting to log stream...".to_string()
-        } else {
-            dep.logs.clone()
-        }
+    let (title, placeholder) = if view.live {
+        ("● Live Stream", "")
+    } else if deployment.is_some() {
+        ("Logs", "Waiting for output...")
     } else {
-        "Loading logs...".to_string()
+        ("Logs", "Loading logs...")
     };
+    if summary_height > 0 {
+        view.summary.render(f, chunks[1]);
+    }
+    view.logs.render(f, chunks[2], title, placeholder);
+    if let Some(dialog) = &view.export {
+        dialog.render(f, area);
+    }
+}
 
-    let logs_widget = Paragraph::new(logs_content)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(if !app.live_logs.is_empty() { "● Live Stream" } else { "Logs" }),
-        )
-        .wrap(Wrap { trim: false })
-        .scroll((app.log_scroll, 0));
-
-    f.render_widget(logs_widget, chunks[1]);
-
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-            Span::raw(" Back | "),
-            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-            Span::raw(" Scroll | "),
-            Span::styled("Home/End", Style::default().fg(Color::Yellow)),
-            Span::raw(" Top/Bottom | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .style(Style::default().fg(Color::DarkGray))
-        .block(Block::default().borders(Borders::ALL));
-    f.render_widget(footer, chunks[2]);
+/// `✓ Clone 1.2s › ● Install 8.4s › ○ Build ...`
+fn step_tracker(phases: &BuildPhases) -> Line<'static> {
+    let mut spans = Vec::new();
+    for phase in Phase::ALL {
+        if !spans.is_empty() {
+            spans.push(Span::styled(" › ", Style::default().fg(Color::DarkGray)));
+        }
+        let step = phases.step(phase);
+        let (symbol, color) = match step {
+            Some(step) if step.failed => ("✗", Color::Red),
+            Some(_) if phases.is_done(phase) => ("✓", Color::Green),
+            Some(_) => ("●", Color::Yellow),
+            // Not every build has every phase; a Dockerfile has no plan
+            None if phases.is_done(phase) => ("-", Color::DarkGray),
+            None => ("○", Color::DarkGray),
+        };
+        let mut label = format!("{} {}", symbol, phase.label());
+        if let Some(elapsed) = step.and_then(|step| step.elapsed()) {
+            label.push(' ');
+            label.push_str(&format_elapsed(elapsed));
+        }
+        spans.push(Span::styled(label, Style::default().fg(color)));
+    }
+    Line::from(spans)
 }
@@ -1,16 +1,94 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
-use crate::ui::app::App;
+use anyhow::Result;
+use crossterm::event::Event;
+use crate::ui::action::Action;
+use crate::ui::app::{App, Screen};
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::list_filter::{ListFilter, SortKey};
 
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+const SORTS: &[SortKey] = &[SortKey::Name, SortKey::Status, SortKey::Port];
+
+pub struct DeploymentsView {
+    filter: ListFilter,
+}
+
+impl DeploymentsView {
+    pub fn new() -> Self {
+        Self { filter: ListFilter::new(SORTS) }
+    }
+}
+
+impl Component for DeploymentsView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        render(f, area, app, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let rows = self.filter.apply(&ctx.app.activity, ctx.app);
+        let action = match event {
+            Event::Key(key) => {
+                if self.filter.handle_key(key, &ctx.app.keys) {
+                    return Ok(Transition::Stay);
+                }
+                ctx.app.keys.action(Scope::Deployments, key)
+            }
+            Event::Mouse(mouse) => self.filter.handle_mouse(mouse, &rows),
+            _ => None,
+        };
+        let Some(action) = action else {
+            return Ok(Transition::Stay);
+        };
+
+        if self.filter.navigate(action, &rows) {
+            return Ok(Transition::Stay);
+        }
+        match action {
+            KeyAction::Open => {
+                if let Some(deployment) = self.filter.selected(&rows) {
+                    return Ok(Transition::Open(Screen::DeploymentLogs(deployment.id.clone())));
+                }
+            }
+            KeyAction::Back => return Ok(Transition::Back),
+            KeyAction::Refresh => ctx.dispatch(Action::FetchActivity),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Deployments
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        if self.filter.is_editing() {
+            return self.filter.keymap(keys);
+        }
+        let mut hints = keys.pair_hint(Scope::Deployments, KeyAction::Up, KeyAction::Down, "Navigate");
+        hints.extend(keys.hints(Scope::Deployments, &[KeyAction::Open, KeyAction::Back, KeyAction::Refresh]));
+        hints.extend(self.filter.keymap(keys));
+        hints.extend(keys.hints(Scope::Deployments, &[KeyAction::Quit]));
+        hints
+    }
+
+    fn is_editing(&self) -> bool {
+        self.filter.is_editing()
+    }
+
+    fn on_enter(&mut self, ctx: &mut Ctx) {
+        ctx.dispatch(Action::FetchActivity);
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App, view: &mut DeploymentsView) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
         .split(area);
 
     // Header
@@ -20,10 +98,13 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(header, chunks[0]);
 
     // Deployments list from activity
-    let items: Vec<ListItem> = app
-        .activity
+    let list_area = view.filter.render(f, chunks[1]);
+    let rows = view.filter.apply(&app.activity, app);
+    let selected = view.filter.selected_index(&rows);
+    let items: Vec<ListItem> = rows
         .iter()
-        .map(|d| {
+        .enumerate()
+        .map(|(i, d)| {
             let status_color = match d.status.as_str() {
                 "live" => Color::Green,
                 "building" => Color::Yellow,
@@ -51,7 +132,16 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 symbol, d.status, commit_short, date
             );
 
-            ListItem::new(display_text).style(Style::default().fg(status_color))
+            let style = if i == selected {
+                Style::default()
+                    .fg(status_color)
+                    .add_modifier(Modifier::BOLD)
+                    .bg(Color::DarkGray)
+            } else {
+                Style::default().fg(status_color)
+            };
+
+            ListItem::new(display_text).style(style)
         })
         .collect();
 
@@ -59,25 +149,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Recent Deployments ({})", app.activity.len()))
+                .title(view.filter.title("Recent Deployments", rows.len(), app.activity.len()))
         );
 
-    f.render_widget(list, chunks[1]);
-
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-            Span::raw(" Back | "),
-            Span::styled("r", Style::default().fg(Color::Yellow)),
-            Span::raw(" Refresh | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::DarkGray));
-    f.render_widget(footer, chunks[2]);
+    view.filter.render_list(f, list, list_area, Some(selected));
 }
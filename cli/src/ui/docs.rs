@@ -2,57 +2,91 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use anyhow::Result;
+use crossterm::event::{Event, MouseEventKind};
 use crate::ui::app::App;
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::help::section;
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::mouse::WHEEL_LINES;
 
-pub fn render(f: &mut Frame, area: Rect, _app: &App) {
+/// Every key binding, screen by screen, as currently configured.
+pub struct DocsView {
+    scroll: u16,
+}
+
+impl DocsView {
+    pub fn new() -> Self {
+        Self { scroll: 0 }
+    }
+}
+
+impl Component for DocsView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        render(f, area, app, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                match mouse.kind {
+                    MouseEventKind::ScrollDown => self.scroll = self.scroll.saturating_add(WHEEL_LINES),
+                    MouseEventKind::ScrollUp => self.scroll = self.scroll.saturating_sub(WHEEL_LINES),
+                    _ => {}
+                }
+                return Ok(Transition::Stay);
+            }
+            _ => return Ok(Transition::Stay),
+        };
+
+        match ctx.app.keys.action(Scope::Docs, key) {
+            Some(KeyAction::Back) => return Ok(Transition::Back),
+            Some(KeyAction::Down) => self.scroll = self.scroll.saturating_add(1),
+            Some(KeyAction::Up) => self.scroll = self.scroll.saturating_sub(1),
+            Some(KeyAction::PageDown) => self.scroll = self.scroll.saturating_add(10),
+            Some(KeyAction::PageUp) => self.scroll = self.scroll.saturating_sub(10),
+            Some(KeyAction::Top) => self.scroll = 0,
+            Some(KeyAction::Bottom) => self.scroll = u16::MAX,
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Docs
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        let mut hints = keys.hints(Scope::Docs, &[KeyAction::Back]);
+        hints.extend(keys.pair_hint(Scope::Docs, KeyAction::Up, KeyAction::Down, "Scroll"));
+        hints.extend(keys.hints(Scope::Docs, &[KeyAction::Quit]));
+        hints
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App, view: &mut DocsView) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
         .split(area);
 
     // Header
-    let header = Paragraph::new("Documentation & Help")
+    let header = Paragraph::new("Key Reference")
         .block(Block::default().borders(Borders::ALL).title("Info"))
         .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
     f.render_widget(header, chunks[0]);
 
-    // Content
-    let content_text = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Clickploy CLI Quick Reference", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Navigation:", Style::default().add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from("  ↑↓ / j k   - Navigate lists"),
-        Line::from("  Enter       - Select / View details"),
-        Line::from("  Backspace   - Go back"),
-        Line::from("  Tab         - Next field (forms)"),
-        Line::from("  q / Ctrl+C  - Quit"),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Main Screen Shortcuts:", Style::default().add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from("  n - Create new project"),
-        Line::from("  a - View activity"),
-        Line::from("  d - View deployments"),
-        Line::from("  w - Network overview"),
-        Line::from("  t - Storage management"),
-        Line::from("  s - Settings"),
-        Line::from("  h - Help (this screen)"),
-        Line::from("  r - Refresh current view"),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Project Actions:", Style::default().add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from("  r - Redeploy project"),
-        Line::from("  l - View logs"),
-        Line::from("  c - View settings"),
+    // The shared list keys show up under each screen that takes them
+    let mut content_text = section(&app.keys, Scope::Global);
+    for scope in Scope::ALL.into_iter().filter(|s| !matches!(s, Scope::Global | Scope::List)) {
+        content_text.extend(section(&app.keys, scope));
+    }
+    content_text.extend([
+        Line::from("Keys can be changed in ~/.config/clickploy/keys.toml (see the README)."),
         Line::from(""),
         Line::from(vec![
             Span::styled("For full documentation, visit:", Style::default().add_modifier(Modifier::BOLD)),
@@ -61,26 +95,12 @@ pub fn render(f: &mut Frame, area: Rect, _app: &App) {
             Span::raw("  "),
             Span::styled("http://localhost:8080/docs", Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED)),
         ]),
-    ];
+    ]);
 
+    let max_scroll = (content_text.len() as u16).saturating_sub(chunks[1].height.saturating_sub(2));
+    view.scroll = view.scroll.min(max_scroll);
     let content = Paragraph::new(content_text)
-        .block(Block::default().borders(Borders::ALL).title("Help"))
-        .style(Style::default())
-        .wrap(Wrap { trim: false });
+        .block(Block::default().borders(Borders::ALL).title("Keys"))
+        .scroll((view.scroll, 0));
     f.render_widget(content, chunks[1]);
-
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-            Span::raw(" Back | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::DarkGray));
-    f.render_widget(footer, chunks[2]);
 }
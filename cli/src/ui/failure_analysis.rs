@@ -0,0 +1,420 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use serde::Deserialize;
+
+use crate::config::get_config_path;
+use crate::models::Deployment;
+use crate::ui::ansi;
+
+/// Offending lines shown per finding.
+const MAX_LINES: usize = 3;
+/// Tallest the panel gets, borders included.
+const MAX_HEIGHT: u16 = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Dependencies,
+    Config,
+    Network,
+    Git,
+    Detection,
+    Resources,
+    Other,
+}
+
+impl Category {
+    fn label(self) -> &'static str {
+        match self {
+            Category::Dependencies => "dependencies",
+            Category::Config => "config",
+            Category::Network => "network",
+            Category::Git => "git",
+            Category::Detection => "detection",
+            Category::Resources => "resources",
+            Category::Other => "other",
+        }
+    }
+}
+
+/// A known way for a deployment to fail: lines containing any of the
+/// patterns, and what to do about it.
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    /// A rule in failures.toml with the id of a built-in one replaces it.
+    pub id: String,
+    pub title: String,
+    #[serde(default = "other")]
+    pub category: Category,
+    /// Case is ignored.
+    #[serde(rename = "match")]
+    pub patterns: Vec<String>,
+    pub fix: String,
+}
+
+fn other() -> Category {
+    Category::Other
+}
+
+/// The built-in rules. A line counts for the first rule that matches it, so
+/// specific rules come before the general ones.
+const BUILTIN: &[(&str, &str, Category, &[&str], &str)] = &[
+    (
+        "missing_start_script",
+        "Missing start script",
+        Category::Config,
+        &["missing script: start", "missing script: \"start\"", "no start command could be found"],
+        "Add a \"start\" script to package.json, or set a start command in the project settings.",
+    ),
+    (
+        "missing_build_script",
+        "Missing build script",
+        Category::Config,
+        &["missing script: build", "missing script: \"build\""],
+        "Add a \"build\" script to package.json, or clear the build command in the project settings.",
+    ),
+    (
+        "port_allocated",
+        "Port already allocated",
+        Category::Network,
+        &["port is already allocated", "address already in use", "eaddrinuse"],
+        "Something else on the server holds the project's port. Stop the project or container using it, then redeploy.",
+    ),
+    (
+        "git_auth",
+        "Git authentication failed",
+        Category::Git,
+        &[
+            "authentication failed",
+            "could not read username",
+            "permission denied (publickey)",
+            "repository not found",
+            "terminal prompts disabled",
+        ],
+        "Check the repository URL and the Git token in the project settings; a private repository needs a token that can read it.",
+    ),
+    (
+        "nixpacks_provider",
+        "Nixpacks could not detect the app",
+        Category::Detection,
+        &["unable to generate a build plan", "no provider found", "no providers found"],
+        "Put the app's manifest (package.json, requirements.txt, go.mod...) at the root of the repository, set the runtime in the project settings, or add a Dockerfile.",
+    ),
+    (
+        "out_of_disk",
+        "Out of disk space",
+        Category::Resources,
+        &["no space left on device", "enospc", "disk quota exceeded"],
+        "The server is out of disk space. Remove unused images and containers (docker system prune) and redeploy.",
+    ),
+    (
+        "package_manager",
+        "Package manager error",
+        Category::Dependencies,
+        &["npm err!", "npm error ", "err_pnpm_", "yarn error", "error command failed with exit code", "eresolve"],
+        "Run the install and build commands locally from a clean checkout. Make sure the lockfile is committed and matches package.json.",
+    ),
+];
+
+/// The rules the failure summary looks for, built in and from
+/// `failures.toml`.
+pub struct FailureRules {
+    rules: Vec<Rule>,
+}
+
+impl Default for FailureRules {
+    fn default() -> Self {
+        let rules = BUILTIN
+            .iter()
+            .map(|(id, title, category, patterns, fix)| Rule {
+                id: id.to_string(),
+                title: title.to_string(),
+                category: *category,
+                patterns: patterns.iter().map(|p| p.to_string()).collect(),
+                fix: fix.to_string(),
+            })
+            .collect();
+        Self { rules }
+    }
+}
+
+/// On-disk shape of failures.toml: a list of `[[rule]]` tables.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default)]
+    rule: Vec<Rule>,
+}
+
+impl FailureRules {
+    /// Reads `failures.toml` next to config.toml. Its rules are checked
+    /// before the built-in ones, except those that replace a built-in rule,
+    /// which take its place. Problems with the file are returned to be shown
+    /// rather than failing startup.
+    pub fn load() -> (Self, Vec<String>) {
+        let path = match rules_path() {
+            Ok(path) if path.exists() => path,
+            _ => return (Self::default(), Vec::new()),
+        };
+
+        match fs::read_to_string(&path).context("Failed to read failures.toml") {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) => (Self::default(), vec![format!("{:#}", e)]),
+        }
+    }
+
+    /// The rules from the contents of `failures.toml`, as for [`Self::load`].
+    fn parse(contents: &str) -> (Self, Vec<String>) {
+        let file: RulesFile = match toml::from_str(contents).context("Failed to parse failures.toml") {
+            Ok(file) => file,
+            Err(e) => return (Self::default(), vec![format!("{:#}", e)]),
+        };
+
+        let mut problems = Vec::new();
+        let mut rules = Self::default().rules;
+        let mut added = Vec::new();
+        for rule in file.rule {
+            if rule.patterns.iter().all(|p| p.trim().is_empty()) {
+                problems.push(format!("failures.toml: rule '{}' has nothing to match", rule.id));
+                continue;
+            }
+            match rules.iter_mut().find(|r| r.id == rule.id) {
+                Some(builtin) => *builtin = rule,
+                None => added.push(rule),
+            }
+        }
+        added.append(&mut rules);
+        (Self { rules: added }, problems)
+    }
+
+    /// Finds the lines of `logs` that match a rule, grouped by rule in the
+    /// order the rules are checked.
+    pub fn analyze(&self, logs: &str) -> Vec<Finding> {
+        let patterns: Vec<Vec<String>> = self
+            .rules
+            .iter()
+            .map(|rule| rule.patterns.iter().filter(|p| !p.trim().is_empty()).map(|p| p.to_lowercase()).collect())
+            .collect();
+
+        let mut found: Vec<(usize, Finding)> = Vec::new();
+        let mut style = Style::default();
+        for (number, raw) in logs.lines().enumerate() {
+            let text = ansi::parse(raw, &mut style).text;
+            let lower = text.to_lowercase();
+            let Some(i) = patterns.iter().position(|any| any.iter().any(|p| lower.contains(p.as_str()))) else {
+                continue;
+            };
+            match found.iter_mut().find(|(rule, _)| *rule == i) {
+                Some((_, finding)) => {
+                    finding.count += 1;
+                    if finding.lines.len() < MAX_LINES {
+                        finding.lines.push((number, text.trim().to_string()));
+                    }
+                }
+                None => {
+                    let rule = &self.rules[i];
+                    found.push((
+                        i,
+                        Finding {
+                            title: rule.title.clone(),
+                            category: rule.category,
+                            fix: rule.fix.clone(),
+                            lines: vec![(number, text.trim().to_string())],
+                            count: 1,
+                        },
+                    ));
+                }
+            }
+        }
+        found.sort_by_key(|(rule, _)| *rule);
+        found.into_iter().map(|(_, finding)| finding).collect()
+    }
+}
+
+fn rules_path() -> Result<PathBuf> {
+    Ok(get_config_path()?.with_file_name("failures.toml"))
+}
+
+/// A rule that matched, with the first lines it matched.
+pub struct Finding {
+    pub title: String,
+    pub category: Category,
+    pub fix: String,
+    /// Line numbers from 0, and the text without colour codes.
+    pub lines: Vec<(usize, String)>,
+    /// Lines that matched, including those not kept.
+    pub count: usize,
+}
+
+/// The failure summary of a deployment, worked out again only when its
+/// logs change.
+#[derive(Default)]
+pub struct FailureSummary {
+    /// Deployment and length of the logs the findings are for; `None`
+    /// when the deployment didn't fail.
+    analyzed: Option<(String, usize)>,
+    findings: Vec<Finding>,
+}
+
+impl FailureSummary {
+    pub fn update(&mut self, rules: &FailureRules, deployment: Option<&Deployment>) {
+        let Some(dep) = deployment.filter(|d| d.status == "failed") else {
+            self.analyzed = None;
+            self.findings.clear();
+            return;
+        };
+        let key = (dep.id.clone(), dep.logs.len());
+        if self.analyzed.as_ref() != Some(&key) {
+            self.findings = rules.analyze(&dep.logs);
+            self.analyzed = Some(key);
+        }
+    }
+
+    /// Whether there's a failed deployment to summarise.
+    pub fn is_shown(&self) -> bool {
+        self.analyzed.is_some()
+    }
+
+    /// Rows the panel needs, or 0 when there's nothing to show.
+    pub fn height(&self) -> u16 {
+        if !self.is_shown() {
+            return 0;
+        }
+        let rows: usize = self.findings.iter().map(|f| f.lines.len() + 2).sum();
+        (rows.max(1) as u16 + 2).min(MAX_HEIGHT)
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut lines = Vec::new();
+        for finding in &self.findings {
+            let mut title = vec![
+                Span::styled(format!("✗ {}", finding.title), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  {}", finding.category.label()), dim),
+            ];
+            if finding.count > finding.lines.len() {
+                title.push(Span::styled(format!("  (+{} more)", finding.count - finding.lines.len()), dim));
+            }
+            lines.push(Line::from(title));
+            for (number, text) in &finding.lines {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:>5} │ ", number + 1), dim),
+                    Span::raw(text.clone()),
+                ]));
+            }
+            lines.push(Line::from(Span::styled(format!("  → {}", finding.fix), Style::default().fg(Color::Green))));
+        }
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No known cause found. The end of the log usually says what went wrong.",
+                dim,
+            )));
+        }
+
+        let panel = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Why it failed")
+                    .border_style(Style::default().fg(Color::Red)),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(panel, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+>>> Cloning repository https://github.com/x/y...
+npm ERR! code ELIFECYCLE
+npm ERR! missing script: start
+\x1b[31mnpm ERR!\x1b[0m one
+npm ERR! two
+npm ERR! three
+Error: listen EADDRINUSE: address already in use :::3000
+";
+
+    fn titles(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.title.as_str()).collect()
+    }
+
+    #[test]
+    fn findings_follow_rule_order() {
+        let findings = FailureRules::default().analyze(LOG);
+        assert_eq!(titles(&findings), ["Missing start script", "Port already allocated", "Package manager error"]);
+
+        // The start script line counts only for the more specific rule
+        assert_eq!(findings[0].lines, [(2, "npm ERR! missing script: start".to_string())]);
+        let npm = &findings[2];
+        assert_eq!(npm.count, 4);
+        assert_eq!(npm.lines.len(), MAX_LINES);
+        assert_eq!(npm.lines[1], (3, "npm ERR! one".to_string()));
+        assert_eq!(npm.category, Category::Dependencies);
+    }
+
+    #[test]
+    fn nothing_found_in_clean_logs() {
+        assert!(FailureRules::default().analyze(">>> Build successful!\n").is_empty());
+    }
+
+    #[test]
+    fn file_rule_replaces_builtin_in_place() {
+        let (rules, problems) = FailureRules::parse(r#"
+            [[rule]]
+            id = "package_manager"
+            title = "Registry trouble"
+            category = "network"
+            match = ["NPM ERR! code"]
+            fix = "Check the registry."
+        "#);
+        assert!(problems.is_empty());
+
+        let findings = rules.analyze(LOG);
+        assert_eq!(titles(&findings), ["Missing start script", "Port already allocated", "Registry trouble"]);
+        assert_eq!(findings[2].count, 1);
+        assert_eq!(findings[2].category, Category::Network);
+    }
+
+    #[test]
+    fn new_file_rules_come_first() {
+        let (rules, problems) = FailureRules::parse(r#"
+            [[rule]]
+            id = "elifecycle"
+            title = "Lifecycle script failed"
+            match = ["elifecycle"]
+            fix = "See the script's output."
+
+            [[rule]]
+            id = "empty"
+            title = "Matches nothing"
+            match = ["", "  "]
+            fix = "-"
+        "#);
+        assert_eq!(problems, ["failures.toml: rule 'empty' has nothing to match"]);
+
+        let findings = rules.analyze(LOG);
+        assert_eq!(titles(&findings)[0], "Lifecycle script failed");
+        assert_eq!(findings[0].category, Category::Other);
+        assert_eq!(findings[3].count, 3);
+    }
+
+    #[test]
+    fn bad_file_falls_back_to_builtin() {
+        let (rules, problems) = FailureRules::parse("[[rule]]\nid = \"x\"\nunknown = 1\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(rules.rules.len(), BUILTIN.len());
+    }
+}
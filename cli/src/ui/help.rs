@@ -0,0 +1,96 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+
+const KEY_WIDTH: usize = 22;
+
+/// The keys of one scope as a heading plus one line per action. Built from
+/// the keymap, so it shows whatever keys.toml changed.
+pub fn section(keys: &Keymap, scope: Scope) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(
+        scope.title(),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    for binding in keys.bindings(scope) {
+        let names: Vec<String> = binding.keys.iter().map(|key| key.to_string()).collect();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<width$}", names.join(" / "), width = KEY_WIDTH),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(binding.description),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+/// The `?` overlay: the keys of the current screen, then the global ones.
+pub struct HelpOverlay {
+    scope: Scope,
+    /// Opened while a text field had focus.
+    editing: bool,
+    scroll: u16,
+}
+
+impl HelpOverlay {
+    pub fn open(scope: Scope, editing: bool) -> Self {
+        Self { scope, editing, scroll: 0 }
+    }
+
+    /// Returns false when the overlay should close.
+    pub fn handle_key(&mut self, key: &KeyEvent, keys: &Keymap) -> bool {
+        if keys.action(Scope::Global, key) == Some(KeyAction::Help) {
+            return false;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            _ => {}
+        }
+        true
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect, keys: &Keymap) {
+        let mut lines = section(keys, self.scope);
+        lines.extend(section(keys, Scope::Global));
+        if self.editing {
+            lines.push(Line::from(Span::styled(
+                "While typing, plain letter keys go to the text field.",
+                Style::default().fg(Color::DarkGray),
+            )));
+        } else {
+            lines.pop();
+        }
+
+        let width = area.width.saturating_sub(8).min(72);
+        let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + 2,
+            width,
+            height,
+        };
+        self.scroll = self.scroll.min((lines.len() as u16 + 2).saturating_sub(height));
+
+        let help = Paragraph::new(lines).scroll((self.scroll, 0)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Keys: {} | ↑↓ Scroll | Esc Close", self.scope.title()))
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(help, popup);
+    }
+}
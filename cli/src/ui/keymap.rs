@@ -0,0 +1,749 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::config::get_config_path;
+use crate::ui::component::KeyHint;
+
+/// Where a binding applies: everywhere, on every list-like screen, in forms,
+/// or on one screen.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Scope {
+    Global,
+    List,
+    Form,
+    Projects,
+    ProjectDetail,
+    ProjectSettings,
+    Activity,
+    Deployments,
+    Network,
+    Storage,
+    Settings,
+    Logs,
+    Docs,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 13] = [
+        Scope::Global,
+        Scope::List,
+        Scope::Form,
+        Scope::Projects,
+        Scope::ProjectDetail,
+        Scope::ProjectSettings,
+        Scope::Activity,
+        Scope::Deployments,
+        Scope::Network,
+        Scope::Storage,
+        Scope::Settings,
+        Scope::Logs,
+        Scope::Docs,
+    ];
+
+    /// Section name in keys.toml.
+    pub fn name(self) -> &'static str {
+        match self {
+            Scope::Global => "global",
+            Scope::List => "list",
+            Scope::Form => "form",
+            Scope::Projects => "projects",
+            Scope::ProjectDetail => "project_detail",
+            Scope::ProjectSettings => "project_settings",
+            Scope::Activity => "activity",
+            Scope::Deployments => "deployments",
+            Scope::Network => "network",
+            Scope::Storage => "storage",
+            Scope::Settings => "settings",
+            Scope::Logs => "logs",
+            Scope::Docs => "help",
+        }
+    }
+
+    /// Heading for the scope in the help overlay and the key reference.
+    pub fn title(self) -> &'static str {
+        match self {
+            Scope::Global => "Everywhere",
+            Scope::List => "Lists",
+            Scope::Form => "Forms",
+            Scope::Projects => "Projects",
+            Scope::ProjectDetail => "Project",
+            Scope::ProjectSettings => "Project settings",
+            Scope::Activity => "Activity",
+            Scope::Deployments => "Deployments",
+            Scope::Network => "Network",
+            Scope::Storage => "Storage",
+            Scope::Settings => "Settings",
+            Scope::Logs => "Deployment logs",
+            Scope::Docs => "Key reference",
+        }
+    }
+
+    /// The shared `list` actions this screen responds to.
+    pub fn shared(self) -> &'static [KeyAction] {
+        use KeyAction::*;
+        match self {
+            Scope::Global | Scope::List | Scope::Form => &[],
+            Scope::Projects => &[Up, Down, Top, Bottom, PageUp, PageDown, Filter, Sort, ReverseSort],
+            Scope::Activity | Scope::Deployments | Scope::Storage => {
+                &[Up, Down, Top, Bottom, PageUp, PageDown, Back, Filter, Sort, ReverseSort]
+            }
+            Scope::Logs | Scope::Docs => &[Up, Down, Top, Bottom, PageUp, PageDown, Back],
+            Scope::Settings => &[Up, Down, Top, Bottom, Back],
+            Scope::Network | Scope::ProjectDetail | Scope::ProjectSettings => &[Back],
+        }
+    }
+}
+
+/// Something a key can be bound to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyAction {
+    Quit,
+    Help,
+    Palette,
+    HistoryBack,
+    HistoryForward,
+    Notifications,
+    Dismiss,
+    CancelRequest,
+    Up,
+    Down,
+    Top,
+    Bottom,
+    PageUp,
+    PageDown,
+    Back,
+    Filter,
+    Sort,
+    ReverseSort,
+    Open,
+    Refresh,
+    New,
+    Activity,
+    Deployments,
+    Network,
+    Storage,
+    Docs,
+    Settings,
+    Redeploy,
+    Stop,
+    Restart,
+    Delete,
+    Logs,
+    ProjectSettings,
+    Edit,
+    Reconfigure,
+    RemoveProfile,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    Follow,
+    Wrap,
+    LineNumbers,
+    ScrollLeft,
+    ScrollRight,
+    Copy,
+    Save,
+    Export,
+    Fold,
+    FoldAll,
+    FailureSummary,
+    NextField,
+    PreviousField,
+    Submit,
+    Cancel,
+}
+
+impl KeyAction {
+    /// Key name in keys.toml.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyAction::Quit => "quit",
+            KeyAction::Help => "help",
+            KeyAction::Palette => "palette",
+            KeyAction::HistoryBack => "history_back",
+            KeyAction::HistoryForward => "history_forward",
+            KeyAction::Notifications => "notifications",
+            KeyAction::Dismiss => "dismiss",
+            KeyAction::CancelRequest => "cancel_request",
+            KeyAction::Up => "up",
+            KeyAction::Down => "down",
+            KeyAction::Top => "top",
+            KeyAction::Bottom => "bottom",
+            KeyAction::PageUp => "page_up",
+            KeyAction::PageDown => "page_down",
+            KeyAction::Back => "back",
+            KeyAction::Filter => "filter",
+            KeyAction::Sort => "sort",
+            KeyAction::ReverseSort => "reverse_sort",
+            KeyAction::Open => "open",
+            KeyAction::Refresh => "refresh",
+            KeyAction::New => "new",
+            KeyAction::Activity => "activity",
+            KeyAction::Deployments => "deployments",
+            KeyAction::Network => "network",
+            KeyAction::Storage => "storage",
+            KeyAction::Docs => "key_reference",
+            KeyAction::Settings => "settings",
+            KeyAction::Redeploy => "redeploy",
+            KeyAction::Stop => "stop",
+            KeyAction::Restart => "restart",
+            KeyAction::Delete => "delete",
+            KeyAction::Logs => "logs",
+            KeyAction::ProjectSettings => "project_settings",
+            KeyAction::Edit => "edit",
+            KeyAction::Reconfigure => "reconfigure",
+            KeyAction::RemoveProfile => "remove_profile",
+            KeyAction::Search => "search",
+            KeyAction::NextMatch => "next_match",
+            KeyAction::PreviousMatch => "previous_match",
+            KeyAction::Follow => "follow",
+            KeyAction::Wrap => "wrap",
+            KeyAction::LineNumbers => "line_numbers",
+            KeyAction::ScrollLeft => "scroll_left",
+            KeyAction::ScrollRight => "scroll_right",
+            KeyAction::Copy => "copy",
+            KeyAction::Save => "save",
+            KeyAction::Export => "export",
+            KeyAction::Fold => "fold",
+            KeyAction::FoldAll => "fold_all",
+            KeyAction::FailureSummary => "failure_summary",
+            KeyAction::NextField => "next_field",
+            KeyAction::PreviousField => "previous_field",
+            KeyAction::Submit => "submit",
+            KeyAction::Cancel => "cancel",
+        }
+    }
+}
+
+/// Bindings every preset starts from: scope, action, description, keys.
+const DEFAULTS: &[(Scope, KeyAction, &str, &[&str])] = &[
+    (Scope::Global, KeyAction::Quit, "Quit", &["q"]),
+    (Scope::Global, KeyAction::Help, "Keys for this screen", &["?", "f1"]),
+    (Scope::Global, KeyAction::Palette, "Command palette", &["ctrl+p", ":"]),
+    (Scope::Global, KeyAction::HistoryBack, "Back through visited screens", &["alt+left"]),
+    (Scope::Global, KeyAction::HistoryForward, "Forward through visited screens", &["alt+right"]),
+    (Scope::Global, KeyAction::Notifications, "Notification history", &["m"]),
+    (Scope::Global, KeyAction::Dismiss, "Dismiss the newest notification", &["x"]),
+    (Scope::Global, KeyAction::CancelRequest, "Cancel the latest request", &["esc"]),
+    (Scope::List, KeyAction::Up, "Move up", &["up"]),
+    (Scope::List, KeyAction::Down, "Move down", &["down"]),
+    (Scope::List, KeyAction::Top, "Go to the top", &["home"]),
+    (Scope::List, KeyAction::Bottom, "Go to the bottom", &["end"]),
+    (Scope::List, KeyAction::PageUp, "Page up", &["pageup"]),
+    (Scope::List, KeyAction::PageDown, "Page down", &["pagedown"]),
+    (Scope::List, KeyAction::Back, "Back", &["backspace"]),
+    (Scope::List, KeyAction::Filter, "Filter", &["/"]),
+    (Scope::List, KeyAction::Sort, "Sort", &["o"]),
+    (Scope::List, KeyAction::ReverseSort, "Reverse", &["O"]),
+    (Scope::Form, KeyAction::NextField, "Next field", &["tab"]),
+    (Scope::Form, KeyAction::PreviousField, "Previous field", &["backtab"]),
+    (Scope::Form, KeyAction::Submit, "Create", &["enter"]),
+    (Scope::Form, KeyAction::Cancel, "Cancel", &["esc"]),
+    (Scope::Projects, KeyAction::Open, "Details", &["enter"]),
+    (Scope::Projects, KeyAction::New, "New", &["n"]),
+    (Scope::Projects, KeyAction::Deployments, "Deployments", &["d"]),
+    (Scope::Projects, KeyAction::Network, "Network", &["w"]),
+    (Scope::Projects, KeyAction::Activity, "Activity", &["a"]),
+    (Scope::Projects, KeyAction::Storage, "Storage", &["t"]),
+    (Scope::Projects, KeyAction::Docs, "Key reference", &["h"]),
+    (Scope::Projects, KeyAction::Settings, "Settings", &["s"]),
+    (Scope::Projects, KeyAction::Refresh, "Refresh", &["r"]),
+    (Scope::ProjectDetail, KeyAction::Redeploy, "Redeploy", &["r"]),
+    (Scope::ProjectDetail, KeyAction::Stop, "Stop", &["s"]),
+    (Scope::ProjectDetail, KeyAction::Logs, "View Logs", &["l"]),
+    (Scope::ProjectDetail, KeyAction::ProjectSettings, "Settings", &["c"]),
+    (Scope::ProjectSettings, KeyAction::Edit, "Edit", &["e"]),
+    (Scope::Activity, KeyAction::Open, "View Logs", &["enter"]),
+    (Scope::Activity, KeyAction::Refresh, "Refresh", &["r"]),
+    (Scope::Deployments, KeyAction::Open, "View Logs", &["enter"]),
+    (Scope::Deployments, KeyAction::Refresh, "Refresh", &["r"]),
+    (Scope::Network, KeyAction::Refresh, "Refresh", &["r"]),
+    (Scope::Storage, KeyAction::Open, "View Credentials", &["enter"]),
+    (Scope::Storage, KeyAction::Back, "Back", &["esc"]),
+    (Scope::Storage, KeyAction::New, "New Database", &["n"]),
+    (Scope::Storage, KeyAction::Delete, "Delete", &["d"]),
+    (Scope::Storage, KeyAction::Stop, "Stop", &["s"]),
+    (Scope::Storage, KeyAction::Restart, "Restart", &["r"]),
+    (Scope::Logs, KeyAction::Search, "Search", &["/"]),
+    (Scope::Logs, KeyAction::NextMatch, "Next match", &["n"]),
+    (Scope::Logs, KeyAction::PreviousMatch, "Previous match", &["N"]),
+    (Scope::Logs, KeyAction::Follow, "Follow", &["f"]),
+    (Scope::Logs, KeyAction::Wrap, "Wrap", &["w"]),
+    (Scope::Logs, KeyAction::LineNumbers, "Line numbers", &["#"]),
+    (Scope::Logs, KeyAction::ScrollLeft, "Scroll left", &["left"]),
+    (Scope::Logs, KeyAction::ScrollRight, "Scroll right", &["right"]),
+    (Scope::Logs, KeyAction::Copy, "Copy", &["y"]),
+    (Scope::Logs, KeyAction::Save, "Save", &["s"]),
+    (Scope::Logs, KeyAction::Export, "Export", &["e"]),
+    (Scope::Logs, KeyAction::Fold, "Collapse/expand phase", &["c"]),
+    (Scope::Logs, KeyAction::FoldAll, "Collapse/expand all", &["C"]),
+    (Scope::Logs, KeyAction::FailureSummary, "Failure summary", &["a"]),
+    (Scope::Settings, KeyAction::Open, "Switch Profile", &["enter"]),
+    (Scope::Settings, KeyAction::Reconfigure, "Reconfigure", &["c"]),
+    (Scope::Settings, KeyAction::RemoveProfile, "Remove Profile", &["d"]),
+];
+
+/// Keys the vim preset adds on top of the defaults.
+const VIM: &[(Scope, KeyAction, &[&str])] = &[
+    (Scope::List, KeyAction::Up, &["k"]),
+    (Scope::List, KeyAction::Down, &["j"]),
+    (Scope::List, KeyAction::Top, &["g"]),
+    (Scope::List, KeyAction::Bottom, &["G"]),
+    (Scope::List, KeyAction::PageUp, &["ctrl+u"]),
+    (Scope::List, KeyAction::PageDown, &["ctrl+d"]),
+    (Scope::Logs, KeyAction::ScrollLeft, &["h"]),
+    (Scope::Logs, KeyAction::ScrollRight, &["l"]),
+];
+
+pub const DEFAULT_PRESET: &str = "vim";
+
+/// A key with its modifiers, e.g. `ctrl+p` or `G`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    fn from_event(event: &KeyEvent) -> Self {
+        let mut modifiers = event.modifiers;
+        // Shift is already part of the character ('G'), or of BackTab
+        if matches!(event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code: event.code, modifiers }
+    }
+
+    /// The key event this key produces, to press it on a click.
+    pub fn event(&self) -> KeyEvent {
+        KeyEvent::new(self.code, self.modifiers)
+    }
+
+    /// Keys that type text, which input fields get before global bindings.
+    pub fn is_text(event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char(_))
+            && !event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl FromStr for Key {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // The key comes last; it can be "+" itself, as in "ctrl++"
+        let (prefix, name) = match s.strip_suffix('+') {
+            Some(prefix) if prefix.is_empty() || prefix.ends_with('+') => (prefix, "+"),
+            _ => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in prefix.split('+').filter(|p| !p.is_empty()) {
+            match part.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                other => anyhow::bail!("unknown modifier '{}' in '{}'", other, s),
+            }
+        }
+
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match name.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                f if f.starts_with('f') && f[1..].parse::<u8>().is_ok_and(|n| (1..=12).contains(&n)) => {
+                    KeyCode::F(f[1..].parse().unwrap_or(1))
+                }
+                _ => anyhow::bail!("unknown key '{}'", s),
+            },
+        };
+
+        // Normalise the way `from_event` does
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        let ctrl = !self.modifiers.is_empty();
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if ctrl => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+pub struct Binding {
+    pub scope: Scope,
+    pub action: KeyAction,
+    pub description: &'static str,
+    pub keys: Vec<Key>,
+}
+
+/// Which key does what on which screen. Built from a preset, with the
+/// overrides from `keys.toml` applied on top.
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(DEFAULT_PRESET).unwrap_or_else(|| Self { bindings: Vec::new() })
+    }
+}
+
+/// On-disk shape of keys.toml: an optional preset, then one table per scope
+/// mapping action names to a key or a list of keys.
+#[derive(Deserialize)]
+struct KeysFile {
+    preset: Option<String>,
+    #[serde(flatten)]
+    scopes: BTreeMap<String, BTreeMap<String, OneOrMany>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keymap {
+    /// `vim` (arrows plus j/k, g/G, Ctrl+D/U) or `arrows` (arrow keys,
+    /// Home/End and PgUp/PgDn only).
+    pub fn preset(name: &str) -> Option<Self> {
+        let vim = match name {
+            "vim" => true,
+            "arrows" => false,
+            _ => return None,
+        };
+
+        let bindings = DEFAULTS
+            .iter()
+            .map(|(scope, action, description, keys)| {
+                let mut keys: Vec<&str> = keys.to_vec();
+                if vim {
+                    let extra = VIM.iter().filter(|(s, a, _)| s == scope && a == action);
+                    keys.extend(extra.flat_map(|(_, _, keys)| keys.iter().copied()));
+                }
+                Binding {
+                    scope: *scope,
+                    action: *action,
+                    description,
+                    // The built-in names all parse
+                    keys: keys.iter().filter_map(|k| k.parse().ok()).collect(),
+                }
+            })
+            .collect();
+        Some(Self { bindings })
+    }
+
+    /// Reads `keys.toml` next to config.toml. Problems with the file are
+    /// returned to be shown rather than failing startup; when bindings
+    /// conflict, the preset is used without the overrides.
+    pub fn load() -> (Self, Vec<String>) {
+        let path = match keys_path() {
+            Ok(path) if path.exists() => path,
+            _ => return (Self::default(), Vec::new()),
+        };
+
+        match fs::read_to_string(&path).context("Failed to read keys.toml") {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) => (Self::default(), vec![format!("{:#}", e)]),
+        }
+    }
+
+    /// The keymap from the contents of `keys.toml`, as for [`Self::load`].
+    fn parse(contents: &str) -> (Self, Vec<String>) {
+        let file: KeysFile = match toml::from_str(contents).context("Failed to parse keys.toml") {
+            Ok(file) => file,
+            Err(e) => return (Self::default(), vec![format!("{:#}", e)]),
+        };
+
+        let mut problems = Vec::new();
+        let preset = file.preset.as_deref().unwrap_or(DEFAULT_PRESET);
+        let base = Self::preset(preset).unwrap_or_else(|| {
+            problems.push(format!("keys.toml: unknown preset '{}' (use vim or arrows)", preset));
+            Self::default()
+        });
+
+        let mut keymap = Self::preset(preset).unwrap_or_default();
+        for (scope_name, actions) in file.scopes {
+            let Some(scope) = Scope::ALL.into_iter().find(|s| s.name() == scope_name) else {
+                problems.push(format!("keys.toml: unknown section [{}]", scope_name));
+                continue;
+            };
+            for (action_name, keys) in actions {
+                let Some(binding) = keymap
+                    .bindings
+                    .iter_mut()
+                    .find(|b| b.scope == scope && b.action.name() == action_name)
+                else {
+                    problems.push(format!("keys.toml: [{}] has no action '{}'", scope_name, action_name));
+                    continue;
+                };
+                let keys = match keys {
+                    OneOrMany::One(key) => vec![key],
+                    OneOrMany::Many(keys) => keys,
+                };
+                match keys.iter().map(|k| k.parse()).collect::<Result<Vec<Key>>>() {
+                    Ok(keys) => binding.keys = keys,
+                    Err(e) => problems.push(format!("keys.toml: [{}] {}: {}", scope_name, action_name, e)),
+                }
+            }
+        }
+
+        let conflicts = keymap.conflicts();
+        if !conflicts.is_empty() {
+            problems.extend(conflicts.into_iter().map(|c| format!("keys.toml: {}", c)));
+            problems.push("keys.toml: using the default keys until the conflicts are fixed".to_string());
+            return (base, problems);
+        }
+        (keymap, problems)
+    }
+
+    /// Keys that would do two different things on the same screen.
+    fn conflicts(&self) -> Vec<String> {
+        let mut found = BTreeSet::new();
+        for scope in Scope::ALL {
+            if matches!(scope, Scope::Global | Scope::List) {
+                continue;
+            }
+            let active = self.bindings.iter().filter(|b| {
+                b.scope == scope
+                    || (b.scope == Scope::List && scope.shared().contains(&b.action))
+                    // Only runs while a request is loading, so it may share a key
+                    || (b.scope == Scope::Global && b.action != KeyAction::CancelRequest)
+            });
+
+            let mut seen: Vec<(Key, &Binding)> = Vec::new();
+            for binding in active {
+                for key in &binding.keys {
+                    // Forms take typed text before global bindings
+                    if scope == Scope::Form
+                        && binding.scope == Scope::Global
+                        && matches!(key.code, KeyCode::Char(_))
+                        && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                    {
+                        continue;
+                    }
+                    if let Some((_, other)) = seen.iter().find(|(k, b)| k == key && b.action != binding.action) {
+                        found.insert(format!(
+                            "'{}' is bound to both {}.{} and {}.{}",
+                            key,
+                            other.scope.name(),
+                            other.action.name(),
+                            binding.scope.name(),
+                            binding.action.name(),
+                        ));
+                    }
+                    seen.push((*key, binding));
+                }
+            }
+        }
+        found.into_iter().collect()
+    }
+
+    /// The action `event` triggers in `scope`, including the shared list
+    /// bindings for screens that have them.
+    pub fn action(&self, scope: Scope, event: &KeyEvent) -> Option<KeyAction> {
+        let key = Key::from_event(event);
+        let find = |scope: Scope| {
+            self.bindings
+                .iter()
+                .find(|b| b.scope == scope && b.keys.contains(&key))
+                .map(|b| b.action)
+        };
+        find(scope).or_else(|| find(Scope::List).filter(|action| scope.shared().contains(action)))
+    }
+
+    /// Where `action` is bound for `scope`, looking at the screen's own
+    /// bindings first, then the shared and global ones.
+    fn binding(&self, scope: Scope, action: KeyAction) -> Option<&Binding> {
+        let find = |s: Scope| {
+            self.bindings
+                .iter()
+                .find(|b| b.scope == s && b.action == action && !b.keys.is_empty())
+        };
+        find(scope)
+            .or_else(|| find(Scope::List).filter(|_| scope.shared().contains(&action)))
+            .or_else(|| find(Scope::Global))
+    }
+
+    /// Bound actions of `scope`, followed by the shared list actions it
+    /// responds to. Global ones are left out.
+    pub fn bindings(&self, scope: Scope) -> Vec<&Binding> {
+        let own = self.bindings.iter().filter(|b| b.scope == scope);
+        let shared = self
+            .bindings
+            .iter()
+            .filter(|b| b.scope == Scope::List && scope.shared().contains(&b.action));
+        own.chain(shared).filter(|b| !b.keys.is_empty()).collect()
+    }
+
+    /// The first key bound to `action`, for text that names it.
+    pub fn key(&self, scope: Scope, action: KeyAction) -> Option<String> {
+        self.binding(scope, action).map(|b| b.keys[0].to_string())
+    }
+
+    /// Footer hints for `actions`, skipping the ones that aren't bound.
+    pub fn hints(&self, scope: Scope, actions: &[KeyAction]) -> Vec<KeyHint> {
+        actions
+            .iter()
+            .filter_map(|action| {
+                let binding = self.binding(scope, *action)?;
+                let key = binding.keys[0];
+                Some(KeyHint::new(key.to_string(), binding.description).clickable(key.event()))
+            })
+            .collect()
+    }
+
+    /// One hint for two related actions, e.g. `↑↓ Navigate`.
+    pub fn pair_hint(&self, scope: Scope, first: KeyAction, second: KeyAction, description: &'static str) -> Vec<KeyHint> {
+        match (self.binding(scope, first), self.binding(scope, second)) {
+            (Some(a), Some(b)) => vec![KeyHint::new(format!("{}{}", a.keys[0], b.keys[0]), description)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn keys_path() -> Result<PathBuf> {
+    Ok(get_config_path()?.with_file_name("keys.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> Key {
+        s.parse().unwrap()
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_plus_as_a_key() {
+        assert_eq!(key("+"), Key { code: KeyCode::Char('+'), modifiers: KeyModifiers::NONE });
+        assert_eq!(key("ctrl++"), Key { code: KeyCode::Char('+'), modifiers: KeyModifiers::CONTROL });
+        assert_eq!(key("ctrl+alt++"), Key { code: KeyCode::Char('+'), modifiers: KeyModifiers::CONTROL | KeyModifiers::ALT });
+    }
+
+    #[test]
+    fn shift_is_folded_in() {
+        assert_eq!(key("shift+g"), key("G"));
+        assert_eq!(key("shift+tab"), Key { code: KeyCode::BackTab, modifiers: KeyModifiers::NONE });
+        assert_eq!(key("shift+tab"), key("backtab"));
+        // Matches what the terminal sends
+        assert_eq!(key("shift+g"), Key::from_event(&press(KeyCode::Char('G'), KeyModifiers::SHIFT)));
+        assert_eq!(key("shift+tab"), Key::from_event(&press(KeyCode::BackTab, KeyModifiers::SHIFT)));
+    }
+
+    #[test]
+    fn parses_named_keys() {
+        assert_eq!(key("Ctrl+P"), Key { code: KeyCode::Char('P'), modifiers: KeyModifiers::CONTROL });
+        assert_eq!(key("alt+left"), Key { code: KeyCode::Left, modifiers: KeyModifiers::ALT });
+        assert_eq!(key("space").code, KeyCode::Char(' '));
+        assert_eq!(key("pgdn").code, KeyCode::PageDown);
+        assert_eq!(key("f1").code, KeyCode::F(1));
+        assert_eq!(key("F12").code, KeyCode::F(12));
+        for bad in ["f0", "f13", "fx", "hyper+a", "enterr", ""] {
+            assert!(bad.parse::<Key>().is_err(), "{} parsed", bad);
+        }
+    }
+
+    #[test]
+    fn presets_have_no_conflicts() {
+        for preset in ["vim", "arrows"] {
+            assert_eq!(Keymap::preset(preset).unwrap().conflicts(), Vec::<String>::new(), "{}", preset);
+        }
+        assert!(Keymap::preset("emacs").is_none());
+    }
+
+    #[test]
+    fn overrides_apply() {
+        let (keymap, problems) = Keymap::parse("preset = \"arrows\"\n[logs]\ncopy = [\"ctrl+y\", \"f5\"]\n");
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(keymap.action(Scope::Logs, &press(KeyCode::F(5), KeyModifiers::NONE)), Some(KeyAction::Copy));
+        // The vim extras aren't in the arrows preset
+        assert_eq!(keymap.action(Scope::Logs, &press(KeyCode::Char('j'), KeyModifiers::NONE)), None);
+    }
+
+    #[test]
+    fn conflicting_override_falls_back_to_preset() {
+        let (keymap, problems) = Keymap::parse("[logs]\ncopy = \"j\"\n");
+        assert!(problems.iter().any(|p| p.contains("'j' is bound to both")), "{:?}", problems);
+        assert!(problems.last().unwrap().contains("using the default keys"));
+
+        let j = press(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(keymap.action(Scope::Logs, &j), Keymap::default().action(Scope::Logs, &j));
+        assert_ne!(keymap.action(Scope::Logs, &j), Some(KeyAction::Copy));
+    }
+
+    #[test]
+    fn reports_bad_entries() {
+        let (_, problems) = Keymap::parse("preset = \"emacs\"\n[nowhere]\nx = \"a\"\n[logs]\nfly = \"a\"\ncopy = \"hyper+c\"\n");
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+    }
+}
@@ -0,0 +1,402 @@
+use std::cmp::Ordering;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, ListState, Paragraph},
+    Frame,
+};
+use tui_input::{Input, InputRequest};
+
+use crate::models::{Database, Deployment, Project};
+use crate::ui::app::App;
+use crate::ui::component::KeyHint;
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::mouse::{ListArea, ListClick};
+
+/// How far the page up/down keys move the highlight.
+const PAGE: usize = 10;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Name,
+    /// Most recent deployment (or creation) first.
+    Newest,
+    Status,
+    Port,
+}
+
+impl SortKey {
+    fn label(self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Newest => "last deploy",
+            SortKey::Status => "status",
+            SortKey::Port => "port",
+        }
+    }
+}
+
+/// An item of a list screen that can be narrowed down and reordered.
+pub trait Filterable {
+    /// Identifies the item across refreshes.
+    fn key(&self) -> String;
+
+    /// Text the filter matches against.
+    fn fields(&self, app: &App) -> Vec<String>;
+
+    fn compare(&self, other: &Self, sort: SortKey, app: &App) -> Ordering;
+}
+
+/// The `/` filter bar and sort order of a list screen, plus which item is
+/// highlighted. The highlight follows the item rather than its position, so
+/// it stays put while the list is narrowed down, reordered or refreshed.
+pub struct ListFilter {
+    input: Input,
+    editing: bool,
+    sorts: &'static [SortKey],
+    /// Index into `sorts`; `None` keeps the order the server returned.
+    sort: Option<usize>,
+    reversed: bool,
+    selected: Option<String>,
+    list: ListArea,
+}
+
+impl ListFilter {
+    pub fn new(sorts: &'static [SortKey]) -> Self {
+        Self {
+            input: Input::default(),
+            editing: false,
+            sorts,
+            sort: None,
+            reversed: false,
+            selected: None,
+            list: ListArea::default(),
+        }
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    fn is_active(&self) -> bool {
+        self.editing || !self.input.value().is_empty() || self.sort.is_some()
+    }
+
+    /// Handles the filter and sort keys, and typing into the filter bar.
+    /// Returns false for keys the screen should handle itself, e.g. arrows
+    /// while typing.
+    pub fn handle_key(&mut self, key: &KeyEvent, keys: &Keymap) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if self.editing {
+            match key.code {
+                KeyCode::Esc => {
+                    self.input.reset();
+                    self.editing = false;
+                }
+                KeyCode::Enter => self.editing = false,
+                KeyCode::Backspace if self.input.value().is_empty() => self.editing = false,
+                KeyCode::Backspace => {
+                    self.input.handle(InputRequest::DeletePrevChar);
+                }
+                KeyCode::Left => {
+                    self.input.handle(InputRequest::GoToPrevChar);
+                }
+                KeyCode::Right => {
+                    self.input.handle(InputRequest::GoToNextChar);
+                }
+                KeyCode::Char('u') if ctrl => {
+                    self.input.handle(InputRequest::DeleteLine);
+                }
+                KeyCode::Char(c) if !ctrl => {
+                    self.input.handle(InputRequest::InsertChar(c));
+                }
+                _ => return false,
+            }
+            return true;
+        }
+
+        if key.code == KeyCode::Esc && !self.input.value().is_empty() {
+            self.input.reset();
+            return true;
+        }
+        match keys.action(Scope::List, key) {
+            Some(KeyAction::Filter) => self.editing = true,
+            Some(KeyAction::Sort) => {
+                self.sort = match self.sort {
+                    None => Some(0),
+                    Some(i) if i + 1 < self.sorts.len() => Some(i + 1),
+                    Some(_) => None,
+                };
+            }
+            Some(KeyAction::ReverseSort) if self.sort.is_some() => self.reversed = !self.reversed,
+            _ => return false,
+        }
+        true
+    }
+
+    /// The items that match the filter, in the chosen order.
+    pub fn apply<'a, T: Filterable>(&self, items: &'a [T], app: &App) -> Vec<&'a T> {
+        let terms: Vec<String> = self
+            .input
+            .value()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+
+        let mut rows: Vec<&T> = items
+            .iter()
+            .filter(|item| {
+                if terms.is_empty() {
+                    return true;
+                }
+                let fields: Vec<String> = item.fields(app).iter().map(|s| s.to_lowercase()).collect();
+                terms.iter().all(|term| fields.iter().any(|field| field.contains(term)))
+            })
+            .collect();
+
+        if let Some(sort) = self.sort.map(|i| self.sorts[i]) {
+            rows.sort_by(|a, b| {
+                let order = a.compare(b, sort, app);
+                if self.reversed { order.reverse() } else { order }
+            });
+        }
+        rows
+    }
+
+    /// Position of the highlighted item in `rows`. Falls back to the first
+    /// row while the item is filtered out, and finds it again once the filter
+    /// is cleared.
+    pub fn selected_index<T: Filterable>(&self, rows: &[&T]) -> usize {
+        self.selected
+            .as_ref()
+            .and_then(|key| rows.iter().position(|row| &row.key() == key))
+            .unwrap_or(0)
+    }
+
+    pub fn selected<'a, T: Filterable>(&self, rows: &[&'a T]) -> Option<&'a T> {
+        rows.get(self.selected_index(rows)).copied()
+    }
+
+    pub fn select(&mut self, key: String) {
+        self.selected = Some(key);
+    }
+
+    /// Moves the highlight for the up/down/top/bottom/page keys. Returns
+    /// false for any other action.
+    pub fn navigate<T: Filterable>(&mut self, action: KeyAction, rows: &[&T]) -> bool {
+        if rows.is_empty() {
+            return matches!(
+                action,
+                KeyAction::Up | KeyAction::Down | KeyAction::Top | KeyAction::Bottom | KeyAction::PageUp | KeyAction::PageDown
+            );
+        }
+        let current = self.selected_index(rows);
+        let last = rows.len() - 1;
+        let index = match action {
+            KeyAction::Down => if current == last { 0 } else { current + 1 },
+            KeyAction::Up => current.checked_sub(1).unwrap_or(last),
+            KeyAction::Top => 0,
+            KeyAction::Bottom => last,
+            KeyAction::PageDown => (current + PAGE).min(last),
+            KeyAction::PageUp => current.saturating_sub(PAGE),
+            _ => return false,
+        };
+        self.selected = Some(rows[index].key());
+        true
+    }
+
+    /// The wheel moves the highlight, a click highlights a row and a double
+    /// click opens it. Returns the action to run, as if its key was pressed.
+    pub fn handle_mouse<T: Filterable>(&mut self, mouse: &MouseEvent, rows: &[&T]) -> Option<KeyAction> {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => return Some(KeyAction::Down),
+            MouseEventKind::ScrollUp => return Some(KeyAction::Up),
+            _ => {}
+        }
+        match self.list.click(mouse, rows.len())? {
+            ListClick::Select(row) => {
+                self.selected = Some(rows[row].key());
+                None
+            }
+            ListClick::Open(row) => {
+                self.selected = Some(rows[row].key());
+                Some(KeyAction::Open)
+            }
+        }
+    }
+
+    /// Draws the list scrolled so the highlighted row is visible, and
+    /// remembers where its rows went for `handle_mouse`.
+    pub fn render_list(&mut self, f: &mut Frame, list: List, area: Rect, selected: Option<usize>) {
+        let mut state = ListState::default().with_selected(selected);
+        f.render_stateful_widget(list, area, &mut state);
+        self.list.set(area, state.offset());
+    }
+
+    /// List title with the number of matches, e.g. `Projects (3 of 12)`.
+    pub fn title(&self, name: &str, shown: usize, total: usize) -> String {
+        if shown == total {
+            format!("{} ({})", name, total)
+        } else {
+            format!("{} ({} of {})", name, shown, total)
+        }
+    }
+
+    pub fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        if self.editing {
+            return vec![
+                KeyHint::new("Enter", "Done"),
+                KeyHint::new("Esc", "Clear filter"),
+                KeyHint::new("↑↓", "Navigate"),
+            ];
+        }
+        let mut actions = vec![KeyAction::Filter, KeyAction::Sort];
+        if self.sort.is_some() {
+            actions.push(KeyAction::ReverseSort);
+        }
+        keys.hints(Scope::List, &actions)
+    }
+
+    /// Splits the filter bar off the bottom of `area` while a filter or sort
+    /// order is set. Returns the area left for the list.
+    pub fn render(&self, f: &mut Frame, area: Rect) -> Rect {
+        if !self.is_active() {
+            return area;
+        }
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+
+        let mut spans = vec![
+            Span::styled(" / ", Style::default().fg(Color::Yellow)),
+            Span::raw(self.input.value().to_string()),
+        ];
+        if let Some(i) = self.sort {
+            let reversed = if self.reversed { " (reversed)" } else { "" };
+            spans.push(Span::styled(
+                format!("   sort: {}{}", self.sorts[i].label(), reversed),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), chunks[1]);
+
+        if self.editing {
+            f.set_cursor_position((chunks[1].x + 3 + self.input.visual_cursor() as u16, chunks[1].y));
+        }
+        chunks[0]
+    }
+}
+
+fn status_rank(status: &str) -> u8 {
+    match status {
+        "failed" | "error" => 0,
+        "building" | "pending" => 1,
+        "live" | "running" => 2,
+        "stopped" => 3,
+        _ => 4,
+    }
+}
+
+fn project_name<'a>(app: &'a App, id: &str) -> Option<&'a str> {
+    app.projects.iter().find(|p| p.id == id).map(|p| p.name.as_str())
+}
+
+fn by_name(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+impl Filterable for Project {
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+
+    fn fields(&self, _app: &App) -> Vec<String> {
+        let mut fields = vec![
+            self.name.clone(),
+            self.repo_url.clone(),
+            self.latest_status().to_string(),
+            self.runtime.clone(),
+        ];
+        if let Some(latest) = self.deployments.as_ref().and_then(|deps| deps.first()) {
+            fields.push(latest.commit.clone());
+        }
+        fields
+    }
+
+    fn compare(&self, other: &Self, sort: SortKey, _app: &App) -> Ordering {
+        let last_deploy = |p: &Project| {
+            p.deployments
+                .as_ref()
+                .and_then(|deps| deps.first())
+                .map(|d| d.created_at.clone())
+        };
+        let order = match sort {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Newest => last_deploy(other).cmp(&last_deploy(self)),
+            SortKey::Status => status_rank(self.latest_status()).cmp(&status_rank(other.latest_status())),
+            SortKey::Port => self.port.cmp(&other.port),
+        };
+        order.then_with(|| by_name(&self.name, &other.name))
+    }
+}
+
+impl Filterable for Deployment {
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+
+    fn fields(&self, app: &App) -> Vec<String> {
+        let mut fields = vec![
+            self.project_id.clone(),
+            self.status.clone(),
+            self.commit.clone(),
+        ];
+        if let Some(project) = app.projects.iter().find(|p| p.id == self.project_id) {
+            fields.push(project.name.clone());
+            fields.push(project.repo_url.clone());
+            fields.push(project.runtime.clone());
+        }
+        fields
+    }
+
+    fn compare(&self, other: &Self, sort: SortKey, app: &App) -> Ordering {
+        let name = |d: &Deployment| project_name(app, &d.project_id).unwrap_or(&d.project_id).to_string();
+        let port = |d: &Deployment| app.projects.iter().find(|p| p.id == d.project_id).map(|p| p.port);
+        match sort {
+            SortKey::Name => by_name(&name(self), &name(other)),
+            SortKey::Newest => Ordering::Equal,
+            SortKey::Status => status_rank(&self.status).cmp(&status_rank(&other.status)),
+            SortKey::Port => port(self).cmp(&port(other)),
+        }
+        .then_with(|| other.created_at.cmp(&self.created_at))
+    }
+}
+
+impl Filterable for Database {
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn fields(&self, _app: &App) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.db_type.clone(),
+            self.status.clone(),
+            self.port.to_string(),
+        ]
+    }
+
+    fn compare(&self, other: &Self, sort: SortKey, _app: &App) -> Ordering {
+        let order = match sort {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Newest => other.created_at.cmp(&self.created_at),
+            SortKey::Status => status_rank(&self.status).cmp(&status_rank(&other.status)),
+            SortKey::Port => self.port.cmp(&other.port),
+        };
+        order.then_with(|| by_name(&self.name, &other.name))
+    }
+}
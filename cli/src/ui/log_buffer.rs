@@ -0,0 +1,179 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+use ratatui::style::Style;
+
+use crate::ui::ansi::{self, Styled};
+
+/// Lines kept per log; older ones are dropped as new ones arrive.
+pub const MAX_LINES: usize = 50_000;
+
+pub struct LogLine {
+    pub content: Styled,
+    /// When the line started to come in; `None` for stored output.
+    pub received: Option<DateTime<Local>>,
+}
+
+/// Log output split into lines, with the escape codes already turned into
+/// styles. Lines are numbered from the start of the output, so a number
+/// keeps pointing at the same line after older ones were dropped.
+pub struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    /// Lines dropped from the front to stay under `MAX_LINES`.
+    dropped: usize,
+    /// Output after the last newline, kept raw until the line is complete.
+    partial: String,
+    /// `partial` parsed, so it can be shown while it's still being written.
+    partial_line: Option<LogLine>,
+    /// Bytes of `partial` already parsed into `partial_line`.
+    parsed: usize,
+    /// Style in effect at the end of what's parsed of `partial`.
+    partial_style: Style,
+    /// Style in effect at the start of the next line.
+    style: Style,
+}
+
+impl LogBuffer {
+    pub fn new() -> Self {
+        Self {
+            lines: VecDeque::new(),
+            dropped: 0,
+            partial: String::new(),
+            partial_line: None,
+            parsed: 0,
+            partial_style: Style::default(),
+            style: Style::default(),
+        }
+    }
+
+    /// Adds output that came in at `received`, or stored output if `None`.
+    pub fn push(&mut self, chunk: &str, received: Option<DateTime<Local>>) {
+        // A line keeps the time its first part came in
+        let mut started = self.partial_line.as_ref().map_or(received, |line| line.received);
+        self.partial.push_str(chunk);
+        let Some(end) = self.partial.rfind('\n') else {
+            self.parse_partial(started);
+            return;
+        };
+
+        let rest = self.partial.split_off(end + 1);
+        for raw in std::mem::replace(&mut self.partial, rest).lines() {
+            let content = ansi::parse(raw, &mut self.style);
+            if self.lines.len() == MAX_LINES {
+                self.lines.pop_front();
+                self.dropped += 1;
+            }
+            self.lines.push_back(LogLine { content, received: started });
+            started = received;
+        }
+        self.partial_line = None;
+        self.parsed = 0;
+        if !self.partial.is_empty() {
+            self.parse_partial(received);
+        }
+    }
+
+    /// Ends a line that is still being written, so the next output starts
+    /// on a line of its own.
+    pub fn end_line(&mut self) {
+        if let Some(line) = self.partial_line.take() {
+            self.push("\n", line.received);
+        }
+    }
+
+    /// Parses what came in of `partial` since last time, so a long line
+    /// that trickles in isn't parsed again from the start for every chunk.
+    fn parse_partial(&mut self, received: Option<DateTime<Local>>) {
+        let line = self.partial_line.get_or_insert_with(|| {
+            self.partial_style = self.style;
+            LogLine { content: Styled::new(self.style), received }
+        });
+        self.parsed += line.content.push_raw(&self.partial[self.parsed..], &mut self.partial_style);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty() && self.partial_line.is_none()
+    }
+
+    /// Number of the oldest line still kept.
+    pub fn first(&self) -> usize {
+        self.dropped
+    }
+
+    /// One past the number of the last complete line.
+    pub fn complete_end(&self) -> usize {
+        self.dropped + self.lines.len()
+    }
+
+    /// One past the number of the last line, including one still being
+    /// written.
+    pub fn end(&self) -> usize {
+        self.complete_end() + usize::from(self.partial_line.is_some())
+    }
+
+    pub fn get(&self, number: usize) -> Option<&LogLine> {
+        match number.checked_sub(self.dropped)? {
+            i if i < self.lines.len() => self.lines.get(i),
+            i if i == self.lines.len() => self.partial_line.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color, Modifier};
+
+    fn text(buffer: &LogBuffer, number: usize) -> &str {
+        &buffer.get(number).unwrap().content.text
+    }
+
+    #[test]
+    fn splits_lines_and_keeps_partial() {
+        let mut buffer = LogBuffer::new();
+        buffer.push("one\ntw", None);
+        assert_eq!((buffer.complete_end(), buffer.end()), (1, 2));
+        assert_eq!(text(&buffer, 1), "tw");
+
+        buffer.push("o\nthree\n", None);
+        assert_eq!(buffer.end(), 3);
+        assert_eq!(text(&buffer, 1), "two");
+        assert_eq!(text(&buffer, 2), "three");
+    }
+
+    #[test]
+    fn partial_line_split_inside_escape() {
+        let mut buffer = LogBuffer::new();
+        for chunk in ["\x1b[3", "2mok\x1b", "[1m 1", "0%\r", "100%"] {
+            buffer.push(chunk, None);
+        }
+        assert_eq!(text(&buffer, 0), "100%");
+
+        buffer.push("\n", None);
+        let line = buffer.get(0).unwrap();
+        assert_eq!(line.content.text, "100%");
+        assert_eq!(line.content.runs, [(0, Style::default().fg(Color::Indexed(2)).add_modifier(Modifier::BOLD))]);
+    }
+
+    #[test]
+    fn end_line_completes_partial() {
+        let mut buffer = LogBuffer::new();
+        buffer.push("no newline", None);
+        buffer.end_line();
+        buffer.push("next", None);
+        assert_eq!(text(&buffer, 0), "no newline");
+        assert_eq!(text(&buffer, 1), "next");
+    }
+
+    #[test]
+    fn numbers_survive_dropping() {
+        let mut buffer = LogBuffer::new();
+        for i in 0..MAX_LINES + 5 {
+            buffer.push(&format!("{}\n", i), None);
+        }
+        assert_eq!(buffer.first(), 5);
+        assert!(buffer.get(4).is_none());
+        assert_eq!(text(&buffer, 5), "5");
+    }
+}
@@ -0,0 +1,209 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use serde::Serialize;
+use tui_input::{Input, InputRequest};
+
+use crate::ui::log_buffer::LogBuffer;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    /// The text of the log without colour codes.
+    Plain,
+    /// One JSON object per line, with the time it was received.
+    JsonLines,
+}
+
+impl Format {
+    fn label(self) -> &'static str {
+        match self {
+            Format::Plain => "Plain text",
+            Format::JsonLines => "JSON lines",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Plain => "log",
+            Format::JsonLines => "jsonl",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    line: usize,
+    /// RFC 3339, or null for output that was stored rather than streamed.
+    received: Option<String>,
+    text: &'a str,
+}
+
+/// Writes every line still in `buffer` to `path`. Returns the number of
+/// lines written.
+pub fn write(path: &Path, buffer: &LogBuffer, format: Format) -> Result<usize> {
+    let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut out = BufWriter::new(file);
+    for number in buffer.first()..buffer.end() {
+        let Some(line) = buffer.get(number) else { continue };
+        match format {
+            Format::Plain => writeln!(out, "{}", line.content.text)?,
+            Format::JsonLines => {
+                let json = JsonLine {
+                    line: number + 1,
+                    received: line.received.map(|at| at.format("%Y-%m-%dT%H:%M:%S%.3f%:z").to_string()),
+                    text: &line.content.text,
+                };
+                serde_json::to_writer(&mut out, &json)?;
+                writeln!(out)?;
+            }
+        }
+    }
+    out.flush().with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(buffer.end() - buffer.first())
+}
+
+/// `deployment-<id>.log` in the working directory, numbered so an earlier
+/// save isn't overwritten.
+pub fn default_path(deployment_id: &str, format: Format) -> PathBuf {
+    let path = |n: usize| {
+        let suffix = if n == 0 { String::new() } else { format!("-{}", n) };
+        PathBuf::from(format!("deployment-{}{}.{}", deployment_id, suffix, format.extension()))
+    };
+    (0..).map(path).find(|p| !p.exists()).unwrap_or_else(|| path(0))
+}
+
+/// Puts `text` on the clipboard with an OSC 52 escape sequence, which the
+/// terminal handles, so it works over SSH too. Terminals without support
+/// ignore it.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut out = io::stdout();
+    write!(out, "\x1b]52;c;{}\x07", encoded)?;
+    out.flush()
+}
+
+pub enum ExportAnswer {
+    Pending,
+    Cancel,
+    Write(PathBuf, Format),
+}
+
+/// Asks where to export the log to and in which format.
+pub struct ExportDialog {
+    input: Input,
+    format: Format,
+    /// The file exists and Enter was pressed once; a second Enter
+    /// replaces it.
+    replace: bool,
+}
+
+impl ExportDialog {
+    pub fn new(deployment_id: &str) -> Self {
+        let format = Format::JsonLines;
+        Self {
+            input: Input::new(default_path(deployment_id, format).display().to_string()),
+            format,
+            replace: false,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> ExportAnswer {
+        let request = match key.code {
+            KeyCode::Esc => return ExportAnswer::Cancel,
+            KeyCode::Enter => {
+                let path = expand_home(self.input.value().trim());
+                if path.as_os_str().is_empty() {
+                    return ExportAnswer::Pending;
+                }
+                if path.exists() && !self.replace {
+                    self.replace = true;
+                    return ExportAnswer::Pending;
+                }
+                return ExportAnswer::Write(path, self.format);
+            }
+            // Switching the format switches the extension along with it
+            KeyCode::Tab | KeyCode::BackTab => {
+                let old = self.format;
+                self.format = match old {
+                    Format::Plain => Format::JsonLines,
+                    Format::JsonLines => Format::Plain,
+                };
+                if let Some(stem) = self.input.value().strip_suffix(&format!(".{}", old.extension())) {
+                    self.input = Input::new(format!("{}.{}", stem, self.format.extension()));
+                }
+                self.replace = false;
+                return ExportAnswer::Pending;
+            }
+            KeyCode::Backspace => InputRequest::DeletePrevChar,
+            KeyCode::Left => InputRequest::GoToPrevChar,
+            KeyCode::Right => InputRequest::GoToNextChar,
+            KeyCode::Home => InputRequest::GoToStart,
+            KeyCode::End => InputRequest::GoToEnd,
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => InputRequest::DeleteLine,
+            KeyCode::Char(c) => InputRequest::InsertChar(c),
+            _ => return ExportAnswer::Pending,
+        };
+        self.input.handle(request);
+        self.replace = false;
+        ExportAnswer::Pending
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let width = area.width.clamp(20, 72);
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(7)) / 3,
+            width,
+            height: 7.min(area.height),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Export logs")
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup);
+        f.render_widget(Clear, popup);
+        f.render_widget(block, popup);
+
+        let format = |option: Format| {
+            if option == self.format {
+                Span::styled(format!("[{}]", option.label()), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            } else {
+                Span::styled(format!(" {} ", option.label()), Style::default().fg(Color::DarkGray))
+            }
+        };
+        let hint = if self.replace {
+            Span::styled("The file exists. Enter: Replace it | Esc: Cancel", Style::default().fg(Color::Red))
+        } else {
+            Span::styled("Enter: Export | Tab: Format | Esc: Cancel", Style::default().fg(Color::DarkGray))
+        };
+        let lines = vec![
+            Line::from("File:"),
+            Line::from(vec![Span::styled("> ", Style::default().fg(Color::Yellow)), Span::raw(self.input.value())]),
+            Line::from(vec![Span::raw("Format: "), format(Format::Plain), Span::raw(" "), format(Format::JsonLines)]),
+            Line::from(""),
+            Line::from(hint),
+        ];
+        f.render_widget(Paragraph::new(lines), inner);
+
+        let cursor = (self.input.visual_cursor() as u16).min(inner.width.saturating_sub(3));
+        f.set_cursor_position((inner.x + 2 + cursor, inner.y + 1));
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
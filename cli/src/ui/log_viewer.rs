@@ -0,0 +1,687 @@
+use std::collections::BTreeSet;
+use std::ops::{Range, RangeInclusive};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use chrono::{DateTime, Local};
+use tui_input::{Input, InputRequest};
+use unicode_width::UnicodeWidthChar;
+
+use crate::ui::ansi::Styled;
+use crate::ui::component::KeyHint;
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::log_buffer::LogBuffer;
+use crate::ui::mouse::{contains, WHEEL_LINES};
+
+/// Columns the left/right keys move unwrapped lines by.
+const COLUMNS: usize = 8;
+
+const MATCH: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+const SELECTED: Style = Style::new().bg(Color::DarkGray);
+const CURRENT_MATCH: Style = Style::new().fg(Color::Black).bg(Color::LightRed).add_modifier(Modifier::BOLD);
+
+const SUMMARY: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+/// A run of lines that can be collapsed into one summary row.
+pub struct Section {
+    /// First line; the section runs up to the next one.
+    pub start: usize,
+    /// Shown in place of the lines while collapsed.
+    pub summary: String,
+}
+
+/// A scrollable view of a `LogBuffer` that only lays out the lines on
+/// screen. It follows new output until scrolled away from the end, and has
+/// `/` search, wrapping, line numbers and collapsible sections.
+pub struct LogViewer {
+    buffer: LogBuffer,
+    /// Number of the line at the top of the view.
+    top: usize,
+    /// Keep the end of the log in view as output arrives.
+    follow: bool,
+    wrap: bool,
+    line_numbers: bool,
+    /// Columns scrolled to the right while lines aren't wrapped.
+    column: usize,
+    search: Search,
+    sections: Vec<Section>,
+    /// First lines of the collapsed sections.
+    collapsed: BTreeSet<usize>,
+    /// Line where a mouse selection started and where it ends now.
+    selection: Option<(usize, usize)>,
+    /// Line shown on each row of the last frame.
+    row_lines: Vec<usize>,
+    /// Where the lines went in the last frame.
+    area: Rect,
+    /// Rows of text in the last frame, for paging.
+    height: usize,
+    /// Columns of the log area in the last frame.
+    width: usize,
+    /// Top line that shows the end of the log in the last frame.
+    last_top: usize,
+}
+
+impl LogViewer {
+    pub fn new() -> Self {
+        Self {
+            buffer: LogBuffer::new(),
+            top: 0,
+            follow: true,
+            wrap: true,
+            line_numbers: false,
+            column: 0,
+            search: Search::default(),
+            sections: Vec::new(),
+            collapsed: BTreeSet::new(),
+            selection: None,
+            row_lines: Vec::new(),
+            area: Rect::default(),
+            height: 0,
+            width: 0,
+            last_top: 0,
+        }
+    }
+
+    /// Adds output received at `received`, or stored output if `None`.
+    pub fn push(&mut self, chunk: &str, received: Option<DateTime<Local>>) {
+        self.buffer.push(chunk, received);
+        self.search.update(&self.buffer);
+    }
+
+    /// Drops every line, for when the log is replaced rather than added
+    /// to. Wrapping, line numbers, following and the search are kept.
+    pub fn clear(&mut self) {
+        self.buffer = LogBuffer::new();
+        self.top = 0;
+        self.column = 0;
+        self.sections.clear();
+        self.collapsed.clear();
+        self.selection = None;
+        self.search.run(&self.buffer);
+    }
+
+    pub fn end_line(&mut self) {
+        self.buffer.end_line();
+    }
+
+    pub fn buffer(&self) -> &LogBuffer {
+        &self.buffer
+    }
+
+    /// Replaces the sections, which must be in order. Sections that were
+    /// collapsed stay collapsed.
+    pub fn set_sections(&mut self, sections: Vec<Section>) {
+        self.sections = sections;
+    }
+
+    /// Index of the section `line` is in.
+    fn section_at(&self, line: usize) -> Option<usize> {
+        self.sections.partition_point(|s| s.start <= line).checked_sub(1)
+    }
+
+    /// The lines of the collapsed section `line` is in.
+    fn fold(&self, line: usize) -> Option<Range<usize>> {
+        let i = self.section_at(line)?;
+        let start = self.sections[i].start;
+        if !self.collapsed.contains(&start) {
+            return None;
+        }
+        let end = self.sections.get(i + 1).map_or(self.buffer.end(), |next| next.start);
+        Some(start.max(self.buffer.first())..end)
+    }
+
+    fn toggle(&mut self, start: usize) {
+        if !self.collapsed.remove(&start) {
+            self.collapsed.insert(start);
+        }
+    }
+
+    /// The line on the row after `line`'s, skipping collapsed lines.
+    fn next_line(&self, line: usize) -> usize {
+        self.fold(line).map_or(line + 1, |fold| fold.end)
+    }
+
+    /// The line on the row before `line`'s. A collapsed section is shown
+    /// on the row of its first line.
+    fn previous_line(&self, line: usize) -> Option<usize> {
+        let previous = line.checked_sub(1).filter(|l| *l >= self.buffer.first())?;
+        Some(self.fold(previous).map_or(previous, |fold| fold.start))
+    }
+
+    /// The lines selected with the mouse that are still kept.
+    fn selection(&self) -> Option<RangeInclusive<usize>> {
+        let (start, end) = self.selection?;
+        let range = start.min(end).max(self.buffer.first())..=start.max(end).min(self.buffer.end().saturating_sub(1));
+        (!range.is_empty()).then_some(range)
+    }
+
+    /// The selected lines, or the whole log when nothing is selected, as
+    /// plain text. Returns the number of lines too.
+    pub fn copy_text(&self) -> (usize, String) {
+        let range = self.selection().unwrap_or(self.buffer.first()..=self.buffer.end().saturating_sub(1));
+        let lines: Vec<&str> = range
+            .filter_map(|number| self.buffer.get(number))
+            .map(|line| line.content.text.as_str())
+            .collect();
+        (lines.len(), lines.join("\n"))
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.search.editing
+    }
+
+    /// Handles the scrolling, search and display keys of the logs screen,
+    /// and typing into the search bar. Returns false for keys the screen
+    /// should handle itself.
+    pub fn handle_key(&mut self, key: &KeyEvent, keys: &Keymap) -> bool {
+        if self.search.editing {
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            let request = match key.code {
+                KeyCode::Esc => {
+                    self.search = Search::default();
+                    return true;
+                }
+                KeyCode::Enter => {
+                    self.search.editing = false;
+                    return true;
+                }
+                KeyCode::Backspace if self.search.input.value().is_empty() => {
+                    self.search.editing = false;
+                    return true;
+                }
+                KeyCode::Backspace => InputRequest::DeletePrevChar,
+                KeyCode::Left => InputRequest::GoToPrevChar,
+                KeyCode::Right => InputRequest::GoToNextChar,
+                KeyCode::Char('u') if ctrl => InputRequest::DeleteLine,
+                KeyCode::Char(c) if !ctrl => InputRequest::InsertChar(c),
+                _ => return false,
+            };
+            self.search.input.handle(request);
+            self.search.run(&self.buffer);
+            // Jump to the first match from where the search started
+            self.search.current = self.search.matches.iter().position(|(line, _)| *line >= self.search.origin);
+            self.show_current_match();
+            return true;
+        }
+
+        if key.code == KeyCode::Esc && self.selection.is_some() {
+            self.selection = None;
+            return true;
+        }
+        if key.code == KeyCode::Esc && !self.search.query.is_empty() {
+            self.search = Search::default();
+            return true;
+        }
+        let page = self.height.saturating_sub(1).max(1);
+        match keys.action(Scope::Logs, key) {
+            Some(KeyAction::Up) => self.scroll_up(1),
+            Some(KeyAction::Down) => self.scroll_down(1),
+            Some(KeyAction::PageUp) => self.scroll_up(page),
+            Some(KeyAction::PageDown) => self.scroll_down(page),
+            Some(KeyAction::Top) => {
+                self.follow = false;
+                self.top = self.buffer.first();
+            }
+            // Going to the end picks up new output again
+            Some(KeyAction::Bottom) => self.follow = true,
+            Some(KeyAction::Follow) => self.follow = !self.follow,
+            Some(KeyAction::Wrap) => {
+                self.wrap = !self.wrap;
+                self.column = 0;
+            }
+            Some(KeyAction::LineNumbers) => self.line_numbers = !self.line_numbers,
+            Some(KeyAction::ScrollLeft) if !self.wrap => self.column = self.column.saturating_sub(COLUMNS),
+            Some(KeyAction::ScrollRight) if !self.wrap => self.column += COLUMNS,
+            Some(KeyAction::Search) => {
+                self.search = Search {
+                    editing: true,
+                    origin: self.top,
+                    ..Search::default()
+                };
+            }
+            Some(KeyAction::NextMatch) => self.next_match(true),
+            Some(KeyAction::PreviousMatch) => self.next_match(false),
+            Some(KeyAction::Fold) => {
+                if let Some(i) = self.section_at(self.top) {
+                    self.toggle(self.sections[i].start);
+                }
+            }
+            // Everything but the latest section, or expand them all again
+            Some(KeyAction::FoldAll) if !self.sections.is_empty() => {
+                let earlier: Vec<usize> = self.sections[..self.sections.len() - 1].iter().map(|s| s.start).collect();
+                if earlier.iter().all(|start| self.collapsed.contains(start)) {
+                    self.collapsed.clear();
+                } else {
+                    self.collapsed.extend(earlier);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// The wheel scrolls; clicking a line selects it and dragging selects
+    /// the lines in between, scrolling when dragged past the edge.
+    pub fn handle_mouse(&mut self, mouse: &MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll_down(WHEEL_LINES as usize),
+            MouseEventKind::ScrollUp => self.scroll_up(WHEEL_LINES as usize),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let line = contains(self.area, mouse)
+                    .then(|| self.row_lines.get((mouse.row - self.area.y) as usize).copied())
+                    .flatten();
+                // Clicking a collapsed section opens it
+                if let Some(fold) = line.and_then(|line| self.fold(line)) {
+                    self.collapsed.remove(&fold.start);
+                    self.selection = None;
+                    return;
+                }
+                self.selection = line.map(|line| (line, line));
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let Some((start, _)) = self.selection else { return };
+                let end = if mouse.row < self.area.y {
+                    self.scroll_up(1);
+                    self.top
+                } else if mouse.row >= self.area.bottom() {
+                    self.scroll_down(1);
+                    self.row_lines.last().copied().unwrap_or(start)
+                } else {
+                    let row = (mouse.row - self.area.y) as usize;
+                    self.row_lines.get(row).or(self.row_lines.last()).copied().unwrap_or(start)
+                };
+                self.selection = Some((start, end));
+            }
+            _ => {}
+        }
+    }
+
+    /// Moving up pauses following, so new output doesn't pull the view
+    /// away from what is being read.
+    fn scroll_up(&mut self, lines: usize) {
+        self.follow = false;
+        for _ in 0..lines {
+            match self.previous_line(self.top) {
+                Some(line) => self.top = line,
+                None => break,
+            }
+        }
+    }
+
+    fn scroll_down(&mut self, lines: usize) {
+        if self.follow {
+            return;
+        }
+        for _ in 0..lines {
+            let next = self.next_line(self.top);
+            if next > self.last_top {
+                break;
+            }
+            self.top = next;
+        }
+    }
+
+    fn next_match(&mut self, forward: bool) {
+        let matches = &self.search.matches;
+        if matches.is_empty() {
+            return;
+        }
+        let last = matches.len() - 1;
+        self.search.current = Some(match (self.search.current, forward) {
+            (Some(i), true) => if i == last { 0 } else { i + 1 },
+            (Some(i), false) => i.checked_sub(1).unwrap_or(last),
+            (None, true) => matches.iter().position(|(line, _)| *line >= self.top).unwrap_or(0),
+            (None, false) => matches.iter().rposition(|(line, _)| *line < self.top).unwrap_or(last),
+        });
+        self.show_current_match();
+    }
+
+    /// Scrolls the current match into view, a third of the way down.
+    fn show_current_match(&mut self) {
+        let Some(&(line, start)) = self.search.current.and_then(|i| self.search.matches.get(i)) else {
+            return;
+        };
+        self.follow = false;
+        if let Some(fold) = self.fold(line) {
+            self.collapsed.remove(&fold.start);
+        }
+        if line < self.top || line >= self.top + self.height {
+            self.top = line.saturating_sub(self.height / 3).max(self.buffer.first());
+        }
+        if !self.wrap && let Some(text) = self.buffer.get(line) {
+            let column = width(&text.content.text[..start]);
+            if column < self.column || column >= self.column + self.text_width() {
+                self.column = column.saturating_sub(self.text_width() / 3);
+            }
+        }
+    }
+
+    /// Columns taken by the line numbers.
+    fn gutter(&self) -> usize {
+        if self.line_numbers { self.buffer.end().to_string().len() + 1 } else { 0 }
+    }
+
+    fn text_width(&self) -> usize {
+        self.width.saturating_sub(self.gutter()).max(1)
+    }
+
+    pub fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        if self.search.editing {
+            return vec![KeyHint::new("Enter", "Done"), KeyHint::new("Esc", "Clear search")];
+        }
+        let mut hints = keys.pair_hint(Scope::Logs, KeyAction::Up, KeyAction::Down, "Scroll");
+        hints.extend(keys.hints(Scope::Logs, &[KeyAction::Search]));
+        if !self.search.query.is_empty() {
+            hints.extend(keys.pair_hint(Scope::Logs, KeyAction::NextMatch, KeyAction::PreviousMatch, "Next/Previous"));
+        }
+        hints.extend(keys.hints(Scope::Logs, &[KeyAction::Follow, KeyAction::Wrap, KeyAction::LineNumbers]));
+        if !self.wrap {
+            hints.extend(keys.pair_hint(Scope::Logs, KeyAction::ScrollLeft, KeyAction::ScrollRight, "Sideways"));
+        }
+        if !self.sections.is_empty() {
+            hints.extend(keys.hints(Scope::Logs, &[KeyAction::Fold]));
+        }
+        hints
+    }
+
+    /// Draws the lines in view inside a block titled `title`, with a status
+    /// bar below. `placeholder` is shown while there is no output yet.
+    pub fn render(&mut self, f: &mut Frame, area: Rect, title: &str, placeholder: &str) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+        let block = Block::default().borders(Borders::ALL).title(title.to_string());
+        let inner = block.inner(chunks[0]);
+        f.render_widget(block, chunks[0]);
+
+        self.height = inner.height as usize;
+        self.width = inner.width as usize;
+        if self.buffer.is_empty() {
+            f.render_widget(Paragraph::new(placeholder.to_string()), inner);
+        } else {
+            self.render_lines(f, inner);
+        }
+        self.render_status(f, chunks[1]);
+    }
+
+    fn render_lines(&mut self, f: &mut Frame, area: Rect) {
+        let gutter = self.gutter();
+        let width = self.text_width();
+        let (first, end) = (self.buffer.first(), self.buffer.end());
+
+        // The top line that puts the last line at the bottom
+        self.last_top = {
+            let mut rows = 0;
+            let mut top = end;
+            while let Some(previous) = self.previous_line(top) {
+                rows += match self.buffer.get(previous) {
+                    Some(line) if self.wrap && self.fold(previous).is_none() => wrapped_rows(&line.content.text, width),
+                    _ => 1,
+                };
+                if rows > self.height && top < end {
+                    break;
+                }
+                top = previous;
+            }
+            top.max(first)
+        };
+        self.top = if self.follow {
+            self.last_top
+        } else {
+            // Scrolled into a section that has since been collapsed
+            let top = self.top.clamp(first, self.last_top);
+            self.fold(top).map_or(top, |fold| fold.start)
+        };
+
+        let selection = self.selection();
+        let mut rows: Vec<Line> = Vec::with_capacity(self.height);
+        self.row_lines.clear();
+        let mut number = self.top;
+        while number < end && rows.len() < self.height {
+            if let Some(fold) = self.fold(number) {
+                let i = self.section_at(number).unwrap_or_default();
+                let mut spans = Vec::with_capacity(2);
+                if gutter > 0 {
+                    spans.push(Span::styled(
+                        format!("{:>width$} ", number + 1, width = gutter - 1),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                spans.push(Span::styled(
+                    format!("▸ {} ({})", self.sections[i].summary, lines(fold.len())),
+                    SUMMARY,
+                ));
+                rows.push(Line::from(spans));
+                self.row_lines.push(number);
+                number = fold.end;
+                continue;
+            }
+            let Some(line) = self.buffer.get(number) else { break };
+            let highlights = self.search.highlights(number, &line.content.text);
+            let selected = selection.as_ref().is_some_and(|range| range.contains(&number));
+            for (i, row) in layout(&line.content, &highlights, width, self.wrap, self.column).into_iter().enumerate() {
+                let mut spans = Vec::with_capacity(row.len() + 1);
+                if gutter > 0 {
+                    let label = if i == 0 { (number + 1).to_string() } else { String::new() };
+                    spans.push(Span::styled(
+                        format!("{:>width$} ", label, width = gutter - 1),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                if selected {
+                    spans.extend(row.into_iter().map(|span| span.patch_style(SELECTED)));
+                    spans.push(Span::styled(" ".repeat(width), SELECTED));
+                } else {
+                    spans.extend(row);
+                }
+                rows.push(Line::from(spans));
+                self.row_lines.push(number);
+            }
+            number += 1;
+        }
+        rows.truncate(self.height);
+        self.row_lines.truncate(self.height);
+        self.area = area;
+        f.render_widget(Paragraph::new(rows), area);
+    }
+
+    fn render_status(&self, f: &mut Frame, area: Rect) {
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut spans = vec![if self.follow {
+            Span::styled(" ▼ following", Style::default().fg(Color::Green))
+        } else {
+            Span::styled(" ‖ paused", Style::default().fg(Color::Yellow))
+        }];
+
+        let (first, end) = (self.buffer.first(), self.buffer.end());
+        if end > 0 {
+            let last = self.row_lines.last().map_or(self.top, |line| self.fold(*line).map_or(line + 1, |fold| fold.end));
+            spans.push(Span::styled(format!("   lines {}-{} of {}", self.top + 1, last, end), dim));
+        }
+        if first > 0 {
+            spans.push(Span::styled(format!(" ({} oldest dropped)", first), dim));
+        }
+        if !self.wrap {
+            spans.push(Span::styled("   no wrap", dim));
+        }
+        if let Some(range) = self.selection() {
+            spans.push(Span::styled(format!("   lines selected: {}", range.count()), Style::default().fg(Color::Cyan)));
+        }
+
+        let search = &self.search;
+        if search.editing || !search.query.is_empty() {
+            spans.push(Span::styled("   / ", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(search.input.value().to_string()));
+            let count = match (search.current, search.matches.len()) {
+                (_, 0) if search.query.is_empty() => String::new(),
+                (_, 0) => "  no matches".to_string(),
+                (Some(i), n) => format!("  {} of {}", i + 1, n),
+                (None, n) => format!("  {} matches", n),
+            };
+            spans.push(Span::styled(count, dim));
+        }
+
+        if search.editing {
+            let before: usize = spans[..spans.len() - 2].iter().map(|s| s.width()).sum();
+            f.set_cursor_position((area.x + (before + search.input.visual_cursor()) as u16, area.y));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
+
+/// The `/` search over the complete lines of the buffer. Matches are kept
+/// as they are found, so next/previous don't rescan the whole log.
+#[derive(Default)]
+struct Search {
+    input: Input,
+    editing: bool,
+    query: String,
+    /// Line number and byte offset of every match, in order.
+    matches: Vec<(usize, usize)>,
+    /// Index into `matches` of the one last jumped to.
+    current: Option<usize>,
+    /// Lines before this one have been searched.
+    scanned: usize,
+    /// Top line when the search was opened.
+    origin: usize,
+}
+
+impl Search {
+    /// Searches the whole buffer again for a changed query.
+    fn run(&mut self, buffer: &LogBuffer) {
+        self.query = self.input.value().to_string();
+        self.matches.clear();
+        self.current = None;
+        self.scanned = buffer.first();
+        self.update(buffer);
+    }
+
+    /// Forgets matches on dropped lines and searches the newly completed
+    /// ones.
+    fn update(&mut self, buffer: &LogBuffer) {
+        let dropped = self.matches.partition_point(|(line, _)| *line < buffer.first());
+        self.matches.drain(..dropped);
+        self.current = self.current.and_then(|i| i.checked_sub(dropped));
+        if self.query.is_empty() {
+            self.scanned = buffer.complete_end();
+            return;
+        }
+
+        for number in self.scanned.max(buffer.first())..buffer.complete_end() {
+            if let Some(line) = buffer.get(number) {
+                self.matches.extend(find(&line.content.text, &self.query).map(|start| (number, start)));
+            }
+        }
+        self.scanned = buffer.complete_end();
+    }
+
+    /// Byte ranges of line `number` to highlight, found again so the line
+    /// still being written is highlighted too.
+    fn highlights(&self, number: usize, text: &str) -> Vec<(Range<usize>, Style)> {
+        if self.query.is_empty() {
+            return Vec::new();
+        }
+        let current = self.current.and_then(|i| self.matches.get(i)).copied();
+        find(text, &self.query)
+            .map(|start| {
+                let style = if current == Some((number, start)) { CURRENT_MATCH } else { MATCH };
+                (start..start + self.query.len(), style)
+            })
+            .collect()
+    }
+}
+
+/// Byte offsets of `query` in `text`. Case is ignored unless the query has
+/// capitals in it.
+fn find<'a>(text: &'a str, query: &'a str) -> Box<dyn Iterator<Item = usize> + 'a> {
+    if query.chars().any(char::is_uppercase) {
+        Box::new(text.match_indices(query).map(|(start, _)| start))
+    } else {
+        // ASCII lowering keeps the byte offsets
+        let lower = text.to_ascii_lowercase();
+        let starts: Vec<usize> = lower.match_indices(query).map(|(start, _)| start).collect();
+        Box::new(starts.into_iter())
+    }
+}
+
+fn width(text: &str) -> usize {
+    text.chars().map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// Rows `text` takes when wrapped at `width` columns.
+fn wrapped_rows(text: &str, width: usize) -> usize {
+    let mut rows = 1;
+    let mut column = 0;
+    for w in text.chars().map(|c| c.width().unwrap_or(0)) {
+        if column + w > width && column > 0 {
+            rows += 1;
+            column = 0;
+        }
+        column += w;
+    }
+    rows
+}
+
+/// Cuts a line into rows of styled spans: wrapped at `width` columns, or a
+/// single row starting at `column`. Search highlights go over the colours
+/// of the log itself.
+fn layout(line: &Styled, highlights: &[(Range<usize>, Style)], width: usize, wrap: bool, column: usize) -> Vec<Vec<Span<'static>>> {
+    let mut rows = vec![Vec::new()];
+    let mut span = (String::new(), Style::default());
+    let mut runs = line.runs.iter().peekable();
+    let mut style = Style::default();
+    let mut x = 0;
+
+    for (i, c) in line.text.char_indices() {
+        while let Some((_, run)) = runs.next_if(|(start, _)| *start <= i) {
+            style = *run;
+        }
+        let styled = highlights
+            .iter()
+            .find(|(range, _)| range.contains(&i))
+            .map_or(style, |(_, highlight)| *highlight);
+        let w = c.width().unwrap_or(0);
+
+        if wrap {
+            if x + w > width && x > 0 {
+                end_span(&mut rows, &mut span);
+                rows.push(Vec::new());
+                x = 0;
+            }
+        } else if x < column {
+            x += w;
+            continue;
+        } else if x + w > column + width {
+            break;
+        }
+
+        if span.1 != styled {
+            end_span(&mut rows, &mut span);
+            span.1 = styled;
+        }
+        span.0.push(c);
+        x += w;
+    }
+    end_span(&mut rows, &mut span);
+    rows
+}
+
+fn end_span(rows: &mut [Vec<Span<'static>>], span: &mut (String, Style)) {
+    if !span.0.is_empty()
+        && let Some(row) = rows.last_mut()
+    {
+        row.push(Span::styled(std::mem::take(&mut span.0), span.1));
+    }
+}
+
+/// `1 line`, `2 lines`.
+pub fn lines(count: usize) -> String {
+    if count == 1 { "1 line".to_string() } else { format!("{} lines", count) }
+}
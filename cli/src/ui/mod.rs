@@ -1,4 +1,19 @@
+pub mod action;
+pub mod ansi;
 pub mod app;
+pub mod build_phases;
+pub mod component;
+pub mod confirm;
+pub mod help;
+pub mod navigator;
+pub mod notifications;
+pub mod keymap;
+pub mod list_filter;
+pub mod log_buffer;
+pub mod log_export;
+pub mod log_viewer;
+pub mod mouse;
+pub mod palette;
 pub mod setup;
 pub mod projects;
 pub mod project_detail;
@@ -6,6 +21,8 @@ pub mod project_settings;
 pub mod activity;
 pub mod settings;
 pub mod create_project;
+pub mod create_database;
+pub mod failure_analysis;
 pub mod deployment_logs;
 pub mod deployments;
 pub mod network;
@@ -14,27 +31,23 @@ pub mod docs;
 
 pub use app::{App, Screen};
 pub use setup::SetupState;
-pub use create_project::CreateProjectState;
 
-#[derive(Debug, Clone)]
-pub struct CreateDatabaseState {
-    pub name: String,
-    pub db_type: String,
-    pub focused_field: usize,
-}
-
-impl CreateDatabaseState {
-    pub fn new() -> Self {
-        Self {
-            name: String::new(),
-            db_type: "sqlite".to_string(),
-            focused_field: 0,
-        }
-    }
+use component::Component;
 
-    pub fn reset(&mut self) {
-        self.name.clear();
-        self.db_type = "sqlite".to_string();
-        self.focused_field = 0;
+/// Builds the component that shows `screen`.
+pub fn view_for(screen: &Screen, app: &App) -> Box<dyn Component> {
+    match screen {
+        Screen::Projects => Box::new(projects::ProjectsView::new()),
+        Screen::CreateProject => Box::new(create_project::CreateProjectView::new()),
+        Screen::CreateDatabase => Box::new(create_database::CreateDatabaseView::new()),
+        Screen::ProjectDetail(id) => Box::new(project_detail::ProjectDetailView::new(id.clone())),
+        Screen::ProjectSettings(id) => Box::new(project_settings::ProjectSettingsView::new(id.clone())),
+        Screen::DeploymentLogs(id) => Box::new(deployment_logs::DeploymentLogsView::new(id.clone())),
+        Screen::Deployments => Box::new(deployments::DeploymentsView::new()),
+        Screen::Activity => Box::new(activity::ActivityView::new()),
+        Screen::Network => Box::new(network::NetworkView),
+        Screen::Storage => Box::new(storage::StorageView::new()),
+        Screen::Docs => Box::new(docs::DocsView::new()),
+        Screen::Settings => Box::new(settings::SettingsView::new(app)),
     }
 }
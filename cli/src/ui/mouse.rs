@@ -0,0 +1,65 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Position, Rect};
+
+/// A second click on the same row within this time opens it.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+/// Lines a text view moves per wheel notch.
+pub const WHEEL_LINES: u16 = 3;
+
+pub fn is_left_click(mouse: &MouseEvent) -> bool {
+    mouse.kind == MouseEventKind::Down(MouseButton::Left)
+}
+
+pub fn contains(area: Rect, mouse: &MouseEvent) -> bool {
+    area.contains(Position::new(mouse.column, mouse.row))
+}
+
+pub enum ListClick {
+    Select(usize),
+    Open(usize),
+}
+
+/// Where a bordered list was drawn in the last frame, so clicks can be
+/// mapped to the row under the pointer.
+#[derive(Default)]
+pub struct ListArea {
+    /// Inside the border.
+    area: Rect,
+    /// Index of the first visible row.
+    offset: usize,
+    last_click: Option<(Instant, usize)>,
+}
+
+impl ListArea {
+    pub fn set(&mut self, area: Rect, offset: usize) {
+        self.area = area.inner(Margin::new(1, 1));
+        self.offset = offset;
+    }
+
+    /// The row under the pointer, which may be past the end of the list.
+    pub fn row_at(&self, mouse: &MouseEvent) -> Option<usize> {
+        contains(self.area, mouse).then(|| self.offset + (mouse.row - self.area.y) as usize)
+    }
+
+    /// A left click on one of the first `len` rows. Clicking the same row
+    /// twice in quick succession opens it.
+    pub fn click(&mut self, mouse: &MouseEvent, len: usize) -> Option<ListClick> {
+        if !is_left_click(mouse) {
+            return None;
+        }
+        let row = self.row_at(mouse).filter(|row| *row < len)?;
+        let now = Instant::now();
+        let double = self
+            .last_click
+            .is_some_and(|(time, last)| last == row && now.duration_since(time) < DOUBLE_CLICK);
+        if double {
+            self.last_click = None;
+            Some(ListClick::Open(row))
+        } else {
+            self.last_click = Some((now, row));
+            Some(ListClick::Select(row))
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::ui::app::{App, Screen};
+use crate::ui::component::{Component, Ctx, Transition};
+use crate::ui::view_for;
+
+/// How many screens back can be remembered.
+const MAX_HISTORY: usize = 50;
+
+struct Entry {
+    screen: Screen,
+    view: Box<dyn Component>,
+}
+
+/// Owns the component for `App::screen`, plus the screens visited before
+/// and after it. Views are kept alive in the history, so going back
+/// restores their selection and scroll position.
+pub struct Navigator {
+    current: Entry,
+    back: Vec<Entry>,
+    forward: Vec<Entry>,
+}
+
+impl Navigator {
+    /// Opens the screen `App::screen` is set to.
+    pub fn new(ctx: &mut Ctx) -> Self {
+        let screen = ctx.app.screen.clone();
+        let mut view = view_for(&screen, ctx.app);
+        view.on_enter(ctx);
+        Self {
+            current: Entry { screen, view },
+            back: Vec::new(),
+            forward: Vec::new(),
+        }
+    }
+
+    pub fn current(&mut self) -> &mut dyn Component {
+        self.current.view.as_mut()
+    }
+
+    pub fn go(&mut self, transition: Transition, ctx: &mut Ctx) {
+        let going_back = matches!(transition, Transition::Back);
+        let next = match transition {
+            Transition::Stay => return,
+            Transition::Quit => {
+                ctx.app.should_quit = true;
+                return;
+            }
+            Transition::Open(screen) => {
+                let view = view_for(&screen, ctx.app);
+                self.forward.clear();
+                Entry { screen, view }
+            }
+            Transition::Back => match self.back.pop() {
+                Some(entry) => entry,
+                None => return,
+            },
+            Transition::Forward => match self.forward.pop() {
+                Some(entry) => entry,
+                None => return,
+            },
+        };
+
+        self.current.view.on_leave(ctx);
+        let previous = std::mem::replace(&mut self.current, next);
+        if going_back {
+            self.forward.push(previous);
+        } else {
+            self.back.push(previous);
+            if self.back.len() > MAX_HISTORY {
+                self.back.remove(0);
+            }
+        }
+
+        ctx.app.screen = self.current.screen.clone();
+        self.current.view.on_enter(ctx);
+    }
+
+    /// Forgets everything but the first screen, e.g. after switching to
+    /// another server where the visited projects don't exist.
+    pub fn reset_history(&mut self) {
+        self.back.truncate(1);
+        self.forward.clear();
+    }
+
+    /// The way to the current screen, e.g. `Projects › my-api › Settings`.
+    pub fn breadcrumbs(&self, app: &App) -> String {
+        self.back
+            .iter()
+            .chain(std::iter::once(&self.current))
+            .map(|entry| entry.screen.title(app))
+            .collect::<Vec<_>>()
+            .join(" › ")
+    }
+}
@@ -1,16 +1,49 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
+use anyhow::Result;
+use crossterm::event::Event;
+use crate::ui::action::Action;
 use crate::ui::app::App;
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
 
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+pub struct NetworkView;
+
+impl Component for NetworkView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        render(f, area, app);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let Event::Key(key) = event else {
+            return Ok(Transition::Stay);
+        };
+
+        match ctx.app.keys.action(Scope::Network, key) {
+            Some(KeyAction::Back) => return Ok(Transition::Back),
+            Some(KeyAction::Refresh) => ctx.dispatch(Action::FetchProjects),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Network
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        keys.hints(Scope::Network, &[KeyAction::Back, KeyAction::Refresh, KeyAction::Quit])
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
         .split(area);
 
     // Header
@@ -65,21 +98,4 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         );
 
     f.render_widget(list, chunks[1]);
-
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-            Span::raw(" Back | "),
-            Span::styled("r", Style::default().fg(Color::Yellow)),
-            Span::raw(" Refresh | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::DarkGray));
-    f.render_widget(footer, chunks[2]);
 }
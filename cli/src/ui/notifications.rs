@@ -0,0 +1,281 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+/// How many notifications the history keeps.
+const MAX_HISTORY: usize = 200;
+/// How many toasts are stacked on screen at once.
+const MAX_TOASTS: usize = 3;
+const TOAST_WIDTH: u16 = 50;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// How long a toast stays up. Errors stay until dismissed.
+    fn lifetime(self) -> Option<Duration> {
+        match self {
+            Severity::Info => Some(Duration::from_secs(3)),
+            Severity::Success => Some(Duration::from_secs(5)),
+            Severity::Warning => Some(Duration::from_secs(8)),
+            Severity::Error => None,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Severity::Info => Color::Cyan,
+            Severity::Success => Color::Green,
+            Severity::Warning => Color::Yellow,
+            Severity::Error => Color::Red,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Severity::Info => "i",
+            Severity::Success => "✓",
+            Severity::Warning => "!",
+            Severity::Error => "✗",
+        }
+    }
+}
+
+pub struct Notification {
+    pub severity: Severity,
+    pub text: String,
+    time: chrono::DateTime<chrono::Local>,
+    shown: Instant,
+    /// How often the same notification came in a row.
+    count: usize,
+    dismissed: bool,
+}
+
+impl Notification {
+    fn expired(&self, now: Instant) -> bool {
+        self.dismissed
+            || self
+                .severity
+                .lifetime()
+                .is_some_and(|lifetime| now.duration_since(self.shown) >= lifetime)
+    }
+
+    fn label(&self) -> String {
+        if self.count > 1 {
+            format!("{} (×{})", self.text, self.count)
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// Feedback from key handlers and background actions. Every notification
+/// shows up as a toast for a while and stays in the history, so results
+/// that arrive while looking at another screen are never lost.
+#[derive(Default)]
+pub struct Notifications {
+    /// Oldest first.
+    history: VecDeque<Notification>,
+    /// Notifications added since the history panel was last opened.
+    unread: usize,
+}
+
+impl Notifications {
+    pub fn push(&mut self, severity: Severity, text: impl Into<String>) {
+        let text = text.into();
+        let now = Instant::now();
+
+        // A repeat of the toast still on screen just brings it back up
+        if let Some(last) = self.history.back_mut()
+            && last.severity == severity
+            && last.text == text
+            && !last.expired(now)
+        {
+            last.count += 1;
+            last.shown = now;
+            last.time = chrono::Local::now();
+            return;
+        }
+
+        self.history.push_back(Notification {
+            severity,
+            text,
+            time: chrono::Local::now(),
+            shown: now,
+            count: 1,
+            dismissed: false,
+        });
+        if self.history.len() > MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.unread += 1;
+    }
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(Severity::Info, text);
+    }
+
+    pub fn success(&mut self, text: impl Into<String>) {
+        self.push(Severity::Success, text);
+    }
+
+    pub fn warning(&mut self, text: impl Into<String>) {
+        self.push(Severity::Warning, text);
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(Severity::Error, text);
+    }
+
+    /// Toasts still on screen, newest first.
+    fn visible(&self, now: Instant) -> impl Iterator<Item = &Notification> {
+        self.history
+            .iter()
+            .rev()
+            .filter(move |n| !n.expired(now))
+            .take(MAX_TOASTS)
+    }
+
+    /// Hides the newest toast. Returns false if there was none.
+    pub fn dismiss(&mut self) -> bool {
+        let now = Instant::now();
+        match self.history.iter_mut().rev().find(|n| !n.expired(now)) {
+            Some(notification) => {
+                notification.dismissed = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn unread(&self) -> usize {
+        self.unread
+    }
+
+    /// Draws the toasts stacked in the top right corner of `area`.
+    pub fn render_toasts(&self, f: &mut Frame, area: Rect) {
+        let width = TOAST_WIDTH.min(area.width.saturating_sub(2));
+        let inner_width = width.saturating_sub(2).max(1) as usize;
+        let mut y = area.y + 1;
+
+        for notification in self.visible(Instant::now()) {
+            let label = notification.label();
+            let rows = (label.chars().count() + 2).div_ceil(inner_width).clamp(1, 4) as u16;
+            let height = rows + 2;
+            if y + height > area.y + area.height {
+                break;
+            }
+
+            let toast_area = Rect {
+                x: area.x + area.width - width - 1,
+                y,
+                width,
+                height,
+            };
+            let color = notification.severity.color();
+            let toast = Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("{} ", notification.severity.symbol()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(label),
+            ]))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(color)),
+            )
+            .wrap(Wrap { trim: true });
+            f.render_widget(Clear, toast_area);
+            f.render_widget(toast, toast_area);
+            y += height;
+        }
+    }
+}
+
+/// The scrollable list of past notifications, newest first.
+pub struct HistoryPanel {
+    selected: usize,
+}
+
+impl HistoryPanel {
+    /// Opening the panel marks everything as read.
+    pub fn open(notifications: &mut Notifications) -> Self {
+        notifications.unread = 0;
+        Self { selected: 0 }
+    }
+
+    /// Returns false when the panel should close.
+    pub fn handle_key(&mut self, key: &KeyEvent, notifications: &mut Notifications) -> bool {
+        let len = notifications.history.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('m') | KeyCode::Char('q') => return false,
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(len.saturating_sub(1)),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::PageDown => self.selected = (self.selected + 10).min(len.saturating_sub(1)),
+            KeyCode::PageUp => self.selected = self.selected.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => self.selected = len.saturating_sub(1),
+            KeyCode::Char('c') => {
+                notifications.history.clear();
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        true
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, notifications: &Notifications) {
+        let width = area.width.saturating_sub(8).min(100);
+        let height = area.height.saturating_sub(4);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + 2,
+            width,
+            height,
+        };
+
+        let items: Vec<ListItem> = notifications
+            .history
+            .iter()
+            .rev()
+            .map(|n| {
+                let color = n.severity.color();
+                ListItem::new(Line::from(vec![
+                    Span::styled(n.time.format("%H:%M:%S ").to_string(), Style::default().fg(Color::DarkGray)),
+                    Span::styled(format!("{} ", n.severity.symbol()), Style::default().fg(color)),
+                    Span::styled(n.label(), Style::default().fg(color)),
+                ]))
+            })
+            .collect();
+
+        let title = format!(
+            "Notifications ({}) | ↑↓ Scroll | c Clear | Esc Close",
+            notifications.history.len()
+        );
+        let list = if items.is_empty() {
+            List::new(vec![ListItem::new("Nothing yet")])
+        } else {
+            List::new(items).highlight_style(Style::default().bg(Color::DarkGray))
+        }
+        .block(Block::default().borders(Borders::ALL).title(title));
+
+        let mut state = ListState::default();
+        state.select(Some(self.selected));
+        f.render_widget(Clear, popup);
+        f.render_stateful_widget(list, popup, &mut state);
+    }
+}
@@ -0,0 +1,444 @@
+use crate::config::{get_config_path, write_private};
+use crate::models::Deployment;
+use crate::ui::action::Action;
+use crate::ui::app::{App, Screen};
+use crate::ui::component::{Ctx, Transition};
+use crate::ui::navigator::Navigator;
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tui_input::{Input, InputRequest};
+
+const MAX_RECENT: usize = 10;
+
+/// What choosing a palette entry does.
+pub enum Command {
+    Open(Screen),
+    /// Open a screen and highlight one of its items.
+    Focus(Screen, String),
+    Run(Action),
+}
+
+impl Command {
+    pub fn run(self, nav: &mut Navigator, ctx: &mut Ctx) {
+        match self {
+            Command::Open(screen) => {
+                if ctx.app.screen != screen {
+                    nav.go(Transition::Open(screen), ctx);
+                }
+            }
+            Command::Focus(screen, id) => {
+                if ctx.app.screen != screen {
+                    nav.go(Transition::Open(screen), ctx);
+                }
+                nav.current().focus(&id, ctx.app);
+            }
+            Command::Run(action) => ctx.confirm(action),
+        }
+    }
+}
+
+struct Item {
+    /// Stable across sessions, used to remember recent commands.
+    key: String,
+    label: String,
+    detail: String,
+    /// Changes something on the server, so it is unavailable offline.
+    online: bool,
+    command: Command,
+}
+
+impl Item {
+    fn new(key: String, label: String, detail: String, command: Command) -> Self {
+        Self { key, label, detail, online: false, command }
+    }
+
+    fn online(mut self) -> Self {
+        self.online = true;
+        self
+    }
+}
+
+pub enum Choice {
+    Pending,
+    Cancel,
+    Run(Box<Command>),
+}
+
+/// Ctrl+P / `:` overlay that fuzzy-searches screens, actions and resources.
+pub struct Palette {
+    input: Input,
+    items: Vec<Item>,
+    /// Indexes into `items` with the matched label positions, best first.
+    matches: Vec<(usize, Vec<usize>)>,
+    selected: usize,
+    profile: String,
+    recent: Vec<String>,
+}
+
+impl Palette {
+    pub fn open(ctx: &mut Ctx) -> Self {
+        // Load what hasn't been fetched yet, so it can be searched too
+        if !ctx.app.offline {
+            if ctx.app.activity.is_empty() {
+                ctx.dispatch(Action::FetchActivity);
+            }
+            if ctx.app.databases.is_empty() {
+                ctx.dispatch(Action::FetchStorage);
+            }
+        }
+
+        let app = &*ctx.app;
+        let mut palette = Self {
+            input: Input::default(),
+            items: items(app),
+            matches: Vec::new(),
+            selected: 0,
+            profile: app.active_profile.clone(),
+            recent: load_recent(&app.active_profile),
+        };
+        palette.update_matches();
+        palette
+    }
+
+    /// Rebuilds the entries after new data arrived, keeping the query.
+    pub fn refresh(&mut self, app: &App) {
+        self.items = items(app);
+        self.update_matches();
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent, app: &mut App) -> Choice {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Choice::Cancel,
+            KeyCode::Enter => {
+                let Some((index, _)) = self.matches.get(self.selected) else {
+                    return Choice::Pending;
+                };
+                if self.items[*index].online && !app.require_online() {
+                    return Choice::Cancel;
+                }
+                let item = self.items.swap_remove(*index);
+                self.remember(item.key);
+                return Choice::Run(Box::new(item.command));
+            }
+            KeyCode::Down | KeyCode::Tab => self.move_selection(1),
+            KeyCode::Char('n') if ctrl => self.move_selection(1),
+            KeyCode::Up | KeyCode::BackTab => self.move_selection(-1),
+            KeyCode::Char('p') if ctrl => self.move_selection(-1),
+            KeyCode::Left => {
+                self.input.handle(InputRequest::GoToPrevChar);
+            }
+            KeyCode::Right => {
+                self.input.handle(InputRequest::GoToNextChar);
+            }
+            KeyCode::Char('u') if ctrl => {
+                self.input.handle(InputRequest::DeleteLine);
+                self.update_matches();
+            }
+            KeyCode::Char(c) => {
+                self.input.handle(InputRequest::InsertChar(c));
+                self.update_matches();
+            }
+            KeyCode::Backspace => {
+                self.input.handle(InputRequest::DeletePrevChar);
+                self.update_matches();
+            }
+            _ => {}
+        }
+        Choice::Pending
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.matches.len() as isize;
+        if len > 0 {
+            self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    fn update_matches(&mut self) {
+        let query = self.input.value();
+        self.selected = 0;
+
+        if query.trim().is_empty() {
+            // Recent commands first, then everything in its usual order
+            let mut order: Vec<usize> = (0..self.items.len()).collect();
+            order.sort_by_key(|&i| self.recent_rank(&self.items[i].key).unwrap_or(MAX_RECENT));
+            self.matches = order.into_iter().map(|i| (i, Vec::new())).collect();
+            return;
+        }
+
+        let mut scored: Vec<(i32, usize, Vec<usize>)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| {
+                let (score, positions) = fuzzy_match(query, &item.label)
+                    .or_else(|| fuzzy_match(query, &item.detail).map(|(score, _)| (score - 10, Vec::new())))?;
+                let recency = self
+                    .recent_rank(&item.key)
+                    .map_or(0, |rank| (MAX_RECENT - rank) as i32);
+                Some((score + recency, i, positions))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.matches = scored.into_iter().map(|(_, i, positions)| (i, positions)).collect();
+    }
+
+    fn recent_rank(&self, key: &str) -> Option<usize> {
+        self.recent.iter().position(|k| k == key)
+    }
+
+    fn remember(&mut self, key: String) {
+        self.recent.retain(|k| *k != key);
+        self.recent.insert(0, key);
+        self.recent.truncate(MAX_RECENT);
+        // Recents are a convenience, so failing to save them is not worth reporting
+        let _ = save_recent(&self.profile, &self.recent);
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let width = area.width.saturating_sub(4).min(90);
+        let height = area.height.saturating_sub(4).min(18);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup);
+
+        let input_area = Rect { height: 3, ..popup };
+        let input = Paragraph::new(Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Yellow)),
+            Span::raw(self.input.value()),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title("Command Palette"));
+        f.render_widget(input, input_area);
+        f.set_cursor_position((
+            input_area.x + 3 + self.input.visual_cursor() as u16,
+            input_area.y + 1,
+        ));
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|(index, positions)| {
+                let item = &self.items[*index];
+                let mut spans: Vec<Span> = item
+                    .label
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        if positions.contains(&i) {
+                            Span::styled(c.to_string(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+                        } else {
+                            Span::raw(c.to_string())
+                        }
+                    })
+                    .collect();
+                let recent = if self.recent_rank(&item.key).is_some() { "recent · " } else { "" };
+                spans.push(Span::styled(
+                    format!("  {}{}", recent, item.detail),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list_area = Rect {
+            y: popup.y + 3,
+            height: popup.height.saturating_sub(3),
+            ..popup
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("{} matches | Enter: Run | Esc: Close", self.matches.len())),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ");
+
+        let mut state = ListState::default();
+        if !self.matches.is_empty() {
+            state.select(Some(self.selected));
+        }
+        f.render_stateful_widget(list, list_area, &mut state);
+    }
+}
+
+fn items(app: &App) -> Vec<Item> {
+    let screen = |key: &str, label: &str, screen: Screen| {
+        Item::new(format!("screen:{}", key), label.to_string(), "screen".to_string(), Command::Open(screen))
+    };
+
+    let mut items = vec![
+        screen("projects", "Go to projects", Screen::Projects),
+        screen("activity", "Open activity", Screen::Activity),
+        screen("deployments", "Open deployments", Screen::Deployments),
+        screen("network", "Open network", Screen::Network),
+        screen("storage", "Open storage", Screen::Storage),
+        screen("settings", "Open settings", Screen::Settings),
+        screen("help", "Open the key reference", Screen::Docs),
+        Item::new("new-project".into(), "New project".into(), "action".into(), Command::Open(Screen::CreateProject)).online(),
+        Item::new("new-database".into(), "Create database".into(), "action".into(), Command::Open(Screen::CreateDatabase)).online(),
+        Item::new("refresh".into(), "Refresh projects".into(), "action".into(), Command::Run(Action::FetchProjects)),
+    ];
+
+    for project in &app.projects {
+        let id = &project.id;
+        let name = &project.name;
+        items.push(Item::new(
+            format!("project:{}", id),
+            name.clone(),
+            format!("project · {}", project.repo_url),
+            Command::Open(Screen::ProjectDetail(id.clone())),
+        ));
+        items.push(Item::new(
+            format!("redeploy:{}", id),
+            format!("Redeploy {}", name),
+            "action".into(),
+            Command::Run(Action::Redeploy(id.clone())),
+        ).online());
+        items.push(Item::new(
+            format!("stop:{}", id),
+            format!("Stop {}", name),
+            "action".into(),
+            Command::Run(Action::StopProject(id.clone())),
+        ).online());
+        items.push(Item::new(
+            format!("project-settings:{}", id),
+            format!("Settings for {}", name),
+            "screen".into(),
+            Command::Open(Screen::ProjectSettings(id.clone())),
+        ));
+        if let Some(latest) = project.deployments.iter().flatten().next() {
+            items.push(Item::new(
+                format!("project-logs:{}", id),
+                format!("Logs for {}", name),
+                "screen".into(),
+                Command::Open(Screen::DeploymentLogs(latest.id.clone())),
+            ));
+        }
+    }
+
+    // Deployments from both the activity feed and the project list, once each
+    let mut deployments: Vec<&Deployment> = Vec::new();
+    for deployment in app.activity.iter().chain(app.projects.iter().flat_map(|p| p.deployments.iter().flatten())) {
+        if !deployments.iter().any(|d| d.id == deployment.id) {
+            deployments.push(deployment);
+        }
+    }
+    for deployment in deployments {
+        let project = app
+            .projects
+            .iter()
+            .find(|p| p.id == deployment.project_id)
+            .map_or(deployment.project_id.as_str(), |p| p.name.as_str());
+        items.push(Item::new(
+            format!("deployment:{}", deployment.id),
+            format!("{} {}", deployment.short_commit(), project),
+            format!(
+                "deployment · {} · {}",
+                deployment.status,
+                deployment.created_at.split('T').next().unwrap_or(&deployment.created_at)
+            ),
+            Command::Open(Screen::DeploymentLogs(deployment.id.clone())),
+        ));
+    }
+
+    for db in &app.databases {
+        items.push(Item::new(
+            format!("database:{}", db.id),
+            db.name.clone(),
+            format!("database · {} · {}", db.db_type, db.status),
+            Command::Focus(Screen::Storage, db.id.to_string()),
+        ));
+        items.push(Item::new(
+            format!("restart-database:{}", db.id),
+            format!("Restart database {}", db.name),
+            "action".into(),
+            Command::Run(Action::RestartDatabase(db.id)),
+        ).online());
+        items.push(Item::new(
+            format!("stop-database:{}", db.id),
+            format!("Stop database {}", db.name),
+            "action".into(),
+            Command::Run(Action::StopDatabase(db.id)),
+        ).online());
+    }
+
+    items
+}
+
+/// Matches the query's characters in order, case-insensitively. Returns a
+/// score, higher for consecutive characters and word starts, and the
+/// matched character positions.
+fn fuzzy_match(query: &str, text: &str) -> Option<(i32, Vec<usize>)> {
+    let text: Vec<char> = text.chars().collect();
+    let mut positions: Vec<usize> = Vec::new();
+    let mut score = 0;
+    let mut next = 0;
+
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let found = (next..text.len()).find(|&i| text[i].to_lowercase().eq(q.to_lowercase()))?;
+
+        score += 1;
+        match positions.last() {
+            Some(&last) if last + 1 == found => score += 5,
+            Some(&last) => score -= (found - last - 1).min(5) as i32,
+            None => {}
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 8;
+        }
+
+        positions.push(found);
+        next = found + 1;
+    }
+
+    // Prefer shorter entries when the match is otherwise as good
+    Some((score * 10 - text.len() as i32 / 4, positions))
+}
+
+fn recent_path() -> Result<PathBuf> {
+    Ok(get_config_path()?
+        .parent()
+        .context("Failed to get config directory")?
+        .join("recent_commands.json"))
+}
+
+fn load_recent(profile: &str) -> Vec<String> {
+    let Some(contents) = recent_path().ok().and_then(|path| fs::read_to_string(path).ok()) else {
+        return Vec::new();
+    };
+    let mut all: BTreeMap<String, Vec<String>> = serde_json::from_str(&contents).unwrap_or_default();
+    all.remove(profile).unwrap_or_default()
+}
+
+fn save_recent(profile: &str, recent: &[String]) -> Result<()> {
+    let path = recent_path()?;
+    let mut all: BTreeMap<String, Vec<String>> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default();
+    all.insert(profile.to_string(), recent.to_vec());
+
+    let contents = serde_json::to_string_pretty(&all)
+        .context("Failed to serialize recent commands")?;
+    // Names of projects, deployments and databases on each server
+    write_private(&path, contents.as_bytes())
+        .context("Failed to write recent commands")?;
+
+    Ok(())
+}
@@ -5,16 +5,98 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
-use crate::ui::app::App;
+use anyhow::Result;
+use crossterm::event::Event;
+use crate::ui::action::Action;
+use crate::ui::app::{App, Screen};
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::failure_analysis::FailureSummary;
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
 
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+pub struct ProjectDetailView {
+    id: String,
+    /// Why the latest deployment failed, if it did.
+    summary: FailureSummary,
+}
+
+impl ProjectDetailView {
+    pub fn new(id: String) -> Self {
+        Self { id, summary: FailureSummary::default() }
+    }
+}
+
+impl Component for ProjectDetailView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        let latest = app.selected_project.as_ref().and_then(|p| p.deployments.as_ref()?.first());
+        self.summary.update(&app.failure_rules, latest);
+        render(f, area, app, &self.summary);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let Event::Key(key) = event else {
+            return Ok(Transition::Stay);
+        };
+
+        match ctx.app.keys.action(Scope::ProjectDetail, key) {
+            Some(KeyAction::Back) => return Ok(Transition::Back),
+            Some(KeyAction::Redeploy) if ctx.app.require_online() => {
+                ctx.dispatch(Action::Redeploy(self.id.clone()));
+            }
+            Some(KeyAction::Stop) if ctx.app.require_online() => {
+                ctx.confirm(Action::StopProject(self.id.clone()));
+            }
+            Some(KeyAction::Logs) => {
+                // View Logs for latest deployment
+                if let Some(project) = &ctx.app.selected_project
+                    && let Some(deployments) = &project.deployments {
+                        if let Some(latest) = deployments.first() {
+                            return Ok(Transition::Open(Screen::DeploymentLogs(latest.id.clone())));
+                        } else {
+                            ctx.app.notifications.warning("No deployments found");
+                        }
+                    }
+            }
+            Some(KeyAction::ProjectSettings) => return Ok(Transition::Open(Screen::ProjectSettings(self.id.clone()))),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::ProjectDetail
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        keys.hints(
+            Scope::ProjectDetail,
+            &[
+                KeyAction::Back,
+                KeyAction::Redeploy,
+                KeyAction::Stop,
+                KeyAction::Logs,
+                KeyAction::ProjectSettings,
+                KeyAction::Quit,
+            ],
+        )
+    }
+
+    fn on_enter(&mut self, ctx: &mut Ctx) {
+        // Don't show another project while this one loads
+        if ctx.app.selected_project.as_ref().is_some_and(|p| p.id != self.id) {
+            ctx.app.selected_project = None;
+        }
+        ctx.dispatch(Action::FetchProjectDetail(self.id.clone()));
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App, summary: &FailureSummary) {
     if let Some(project) = &app.selected_project {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(8),
+                Constraint::Length(summary.height()),
                 Constraint::Min(0),
-                Constraint::Length(3),
             ])
             .split(area);
 
@@ -54,6 +136,10 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             .wrap(Wrap { trim: true });
         f.render_widget(info, chunks[0]);
 
+        if summary.is_shown() {
+            summary.render(f, chunks[1]);
+        }
+
         // Deployments
         let deployments = if let Some(deps) = &project.deployments {
             deps.iter()
@@ -98,59 +184,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                     .borders(Borders::ALL)
                     .title("Deployment History")
             );
-        f.render_widget(deployment_list, chunks[1]);
-
-        // Footer
-        let footer_text = vec![
-            Line::from(vec![
-                Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-                Span::raw(" Back | "),
-                Span::styled("r", Style::default().fg(Color::Yellow)),
-                Span::raw(" Redeploy | "),
-                Span::styled("s", Style::default().fg(Color::Yellow)),
-                Span::raw(" Stop | "),
-                Span::styled("l", Style::default().fg(Color::Yellow)),
-                Span::raw(" View Logs | "),
-                Span::styled("c", Style::default().fg(Color::Yellow)),
-                Span::raw(" Settings | "),
-                Span::styled("q", Style::default().fg(Color::Yellow)),
-                Span::raw(" Quit"),
-            ]),
-        ];
-
-        let footer = Paragraph::new(footer_text)
-            .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::DarkGray));
-        f.render_widget(footer, chunks[2]);
-
-        // Show message or error
-        if !app.message.is_empty() && app.error.is_none() {
-            let msg_area = Rect {
-                x: area.width / 4,
-                y: area.height / 2 - 2,
-                width: area.width / 2,
-                height: 5,
-            };
-            
-            let msg_widget = Paragraph::new(app.message.as_str())
-                .style(Style::default().fg(Color::Green))
-                .block(Block::default().borders(Borders::ALL).title("Info"));
-            f.render_widget(msg_widget, msg_area);
-        }
-
-        if let Some(error) = &app.error {
-            let error_area = Rect {
-                x: area.width / 4,
-                y: area.height / 2 - 2,
-                width: area.width / 2,
-                height: 5,
-            };
-            
-            let error_widget = Paragraph::new(error.as_str())
-                .style(Style::default().fg(Color::Red))
-                .block(Block::default().borders(Borders::ALL).title("Error"));
-            f.render_widget(error_widget, error_area);
-        }
+        f.render_widget(deployment_list, chunks[2]);
     } else {
         let loading = Paragraph::new("Loading project details...")
             .block(Block::default().borders(Borders::ALL).title("Project"))
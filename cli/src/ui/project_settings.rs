@@ -1,13 +1,350 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use tui_input::{Input, InputRequest};
+use crate::models::{Project, UpdateProjectRequest};
+use crate::ui::action::{Action, ActionResult};
 use crate::ui::app::App;
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::mouse::{contains, is_left_click};
 
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+/// Runtimes the builder knows; empty lets it pick (Node.js).
+const RUNTIMES: &[&str] = &["", "nodejs", "bun", "deno", "pnpm", "dockerfile"];
+
+const NAME: usize = 0;
+const REPO_URL: usize = 1;
+const GIT_TOKEN: usize = 2;
+const RUNTIME: usize = 3;
+const INSTALL_COMMAND: usize = 4;
+const BUILD_COMMAND: usize = 5;
+const START_COMMAND: usize = 6;
+const FIELDS: usize = 7;
+
+pub struct ProjectSettingsView {
+    id: String,
+    /// The settings being edited, after `e`.
+    form: Option<SettingsForm>,
+}
+
+impl ProjectSettingsView {
+    pub fn new(id: String) -> Self {
+        Self { id, form: None }
+    }
+}
+
+/// The editable settings of a project, as they were when editing started
+/// and as they are now.
+struct SettingsForm {
+    original: UpdateProjectRequest,
+    /// One per field; the runtime is picked from `RUNTIMES` instead, so its
+    /// input stays empty.
+    inputs: [Input; FIELDS],
+    runtime: String,
+    port: i32,
+    /// The project's container is running.
+    live: bool,
+    focused_field: usize,
+    /// The changes are shown for a last look before they're applied.
+    reviewing: bool,
+    /// Applied and waiting for the server.
+    saving: bool,
+    error: Option<String>,
+    /// Where each field was drawn, in `focused_field` order.
+    field_areas: [Rect; FIELDS],
+}
+
+impl SettingsForm {
+    fn new(project: &Project) -> Self {
+        let original = UpdateProjectRequest {
+            name: project.name.clone(),
+            repo_url: project.repo_url.clone(),
+            git_token: String::new(),
+            runtime: project.runtime.clone(),
+            install_command: project.install_command.clone(),
+            build_command: project.build_command.clone(),
+            start_command: project.start_command.clone(),
+        };
+        let inputs = [
+            Input::new(original.name.clone()),
+            Input::new(original.repo_url.clone()),
+            Input::default(),
+            Input::default(),
+            Input::new(original.install_command.clone()),
+            Input::new(original.build_command.clone()),
+            Input::new(original.start_command.clone()),
+        ];
+        Self {
+            runtime: original.runtime.clone(),
+            original,
+            inputs,
+            port: project.port,
+            live: project.latest_status() == "live",
+            focused_field: 0,
+            reviewing: false,
+            saving: false,
+            error: None,
+            field_areas: [Rect::default(); FIELDS],
+        }
+    }
+
+    fn request(&self) -> UpdateProjectRequest {
+        let value = |field: usize| self.inputs[field].value().trim().to_string();
+        UpdateProjectRequest {
+            name: value(NAME),
+            repo_url: value(REPO_URL),
+            git_token: value(GIT_TOKEN),
+            runtime: self.runtime.clone(),
+            install_command: value(INSTALL_COMMAND),
+            build_command: value(BUILD_COMMAND),
+            start_command: value(START_COMMAND),
+        }
+    }
+
+    /// The first problem with the form, and the field it's in.
+    fn validate(&self) -> Result<(), (usize, String)> {
+        let request = self.request();
+        if request.name.is_empty() {
+            return Err((NAME, "The name is required".to_string()));
+        }
+        // The container is named after the project, and Docker only takes
+        // these. Names that came from elsewhere are left alone
+        let valid_name = request.name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && request.name.starts_with(|c: char| c.is_ascii_alphanumeric());
+        if self.renamed() && !valid_name {
+            return Err((NAME, "The name can only have letters, digits, '-', '_' and '.', and must start with a letter or digit".to_string()));
+        }
+        if request.repo_url.is_empty() {
+            return Err((REPO_URL, "The repository URL is required".to_string()));
+        }
+        if !["https://", "http://", "ssh://", "git@"].iter().any(|scheme| request.repo_url.starts_with(scheme))
+            || request.repo_url.contains(char::is_whitespace)
+        {
+            return Err((REPO_URL, "The repository URL should start with https://, ssh:// or git@".to_string()));
+        }
+        if request.git_token.contains(char::is_whitespace) {
+            return Err((GIT_TOKEN, "The Git token can't contain spaces".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Label, old value and new value of each setting that changed.
+    fn changes(&self) -> Vec<(&'static str, String, String)> {
+        let new = self.request();
+        let old = &self.original;
+        let shown = |value: &str, empty: &str| if value.is_empty() { empty.to_string() } else { value.to_string() };
+        let mut changes = Vec::new();
+        let mut compare = |label, old: &str, new: &str, empty| {
+            if old != new {
+                changes.push((label, shown(old, empty), shown(new, empty)));
+            }
+        };
+        compare("Name", &old.name, &new.name, "");
+        compare("Repository URL", &old.repo_url, &new.repo_url, "");
+        compare("Runtime", &old.runtime, &new.runtime, "auto");
+        compare("Install command", &old.install_command, &new.install_command, "default");
+        compare("Build command", &old.build_command, &new.build_command, "default");
+        compare("Start command", &old.start_command, &new.start_command, "default");
+        if !new.git_token.is_empty() {
+            changes.push(("Git token", "••••••••".to_string(), "new token".to_string()));
+        }
+        changes
+    }
+
+    /// The container is named after the project, so renaming it loses
+    /// track of the one running.
+    fn renamed(&self) -> bool {
+        self.inputs[NAME].value().trim() != self.original.name
+    }
+
+    fn cycle_runtime(&mut self, forward: bool) {
+        // A runtime set elsewhere stays available to switch back to
+        let mut runtimes: Vec<&str> = RUNTIMES.to_vec();
+        if !runtimes.contains(&self.original.runtime.as_str()) {
+            runtimes.push(&self.original.runtime);
+        }
+        let current = runtimes.iter().position(|r| *r == self.runtime).unwrap_or(0);
+        let next = (if forward { current + 1 } else { current + runtimes.len() - 1 }) % runtimes.len();
+        self.runtime = runtimes[next].to_string();
+    }
+
+    fn handle_text(&mut self, key: &KeyEvent) {
+        if self.focused_field == RUNTIME {
+            match key.code {
+                KeyCode::Left => self.cycle_runtime(false),
+                KeyCode::Right | KeyCode::Char(' ') => self.cycle_runtime(true),
+                _ => {}
+            }
+            return;
+        }
+        let request = match key.code {
+            KeyCode::Backspace => InputRequest::DeletePrevChar,
+            KeyCode::Delete => InputRequest::DeleteNextChar,
+            KeyCode::Left => InputRequest::GoToPrevChar,
+            KeyCode::Right => InputRequest::GoToNextChar,
+            KeyCode::Home => InputRequest::GoToStart,
+            KeyCode::End => InputRequest::GoToEnd,
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => InputRequest::DeleteLine,
+            KeyCode::Char(c) => InputRequest::InsertChar(c),
+            _ => return,
+        };
+        self.inputs[self.focused_field].handle(request);
+    }
+
+    /// Sends the changes, then redeploys if asked to.
+    fn apply(&mut self, id: &str, redeploy: bool, ctx: &mut Ctx) {
+        if !ctx.app.require_online() {
+            return;
+        }
+        let request = self.request();
+        let stop_first = redeploy && self.live && self.renamed();
+        self.saving = true;
+        self.error = None;
+        ctx.dispatch(Action::UpdateProject { id: id.to_string(), request, redeploy, stop_first });
+    }
+}
+
+impl Component for ProjectSettingsView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        match &mut self.form {
+            Some(form) => render_form(f, area, form),
+            None => render(f, area, app),
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let Some(form) = &mut self.form else {
+            let Event::Key(key) = event else {
+                return Ok(Transition::Stay);
+            };
+            match ctx.app.keys.action(Scope::ProjectSettings, key) {
+                Some(KeyAction::Back) => return Ok(Transition::Back),
+                Some(KeyAction::Edit) if ctx.app.require_online() => {
+                    if let Some(project) = ctx.app.selected_project.as_ref().filter(|p| p.id == self.id) {
+                        self.form = Some(SettingsForm::new(project));
+                    }
+                }
+                _ => {}
+            }
+            return Ok(Transition::Stay);
+        };
+
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                if is_left_click(mouse)
+                    && !form.reviewing
+                    && let Some(field) = form.field_areas.iter().position(|area| contains(*area, mouse))
+                {
+                    form.focused_field = field;
+                }
+                return Ok(Transition::Stay);
+            }
+            _ => return Ok(Transition::Stay),
+        };
+        if form.saving {
+            return Ok(Transition::Stay);
+        }
+
+        if form.reviewing {
+            match key.code {
+                KeyCode::Enter => form.apply(&self.id, false, ctx),
+                KeyCode::Char('r') => form.apply(&self.id, true, ctx),
+                KeyCode::Esc => form.reviewing = false,
+                _ => {}
+            }
+            return Ok(Transition::Stay);
+        }
+
+        match ctx.app.keys.action(Scope::Form, key) {
+            Some(KeyAction::Cancel) => self.form = None,
+            Some(KeyAction::NextField) => form.focused_field = (form.focused_field + 1) % FIELDS,
+            Some(KeyAction::PreviousField) => form.focused_field = (form.focused_field + FIELDS - 1) % FIELDS,
+            Some(KeyAction::Submit) => match form.validate() {
+                Err((field, e)) => {
+                    form.focused_field = field;
+                    form.error = Some(e);
+                }
+                Ok(()) if form.changes().is_empty() => ctx.app.notifications.info("Nothing to change"),
+                Ok(()) => {
+                    form.error = None;
+                    form.reviewing = true;
+                }
+            },
+            _ => form.handle_text(key),
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        if self.form.is_some() { Scope::Form } else { Scope::ProjectSettings }
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        match &self.form {
+            None => keys.hints(Scope::ProjectSettings, &[KeyAction::Back, KeyAction::Edit, KeyAction::Quit]),
+            Some(form) if form.reviewing => vec![
+                KeyHint::new("Enter", "Apply"),
+                KeyHint::new("r", "Apply and redeploy"),
+                KeyHint::new("Esc", "Keep editing"),
+            ],
+            Some(_) => {
+                let mut hints = keys.hints(Scope::Form, &[KeyAction::NextField]);
+                hints.extend(
+                    keys.hints(Scope::Form, &[KeyAction::Submit])
+                        .into_iter()
+                        .map(|hint| KeyHint { description: "Review changes", ..hint }),
+                );
+                hints.extend(keys.hints(Scope::Form, &[KeyAction::Cancel]));
+                hints
+            }
+        }
+    }
+
+    fn is_editing(&self) -> bool {
+        self.form.as_ref().is_some_and(|form| !form.reviewing)
+    }
+
+    fn on_result(&mut self, result: &ActionResult, _app: &mut App) -> Transition {
+        if let ActionResult::ProjectUpdated { id, result, .. } = result
+            && *id == self.id
+            && let Some(form) = &mut self.form
+        {
+            match result {
+                Ok(_) => self.form = None,
+                Err(e) => {
+                    form.saving = false;
+                    form.reviewing = false;
+                    form.error = Some(e.describe("Failed to save settings"));
+                }
+            }
+        }
+        Transition::Stay
+    }
+
+    fn on_leave(&mut self, _ctx: &mut Ctx) {
+        // The result only reaches the current screen, so a form left while
+        // saving would wait for it forever; the app reports it instead
+        if self.form.as_ref().is_some_and(|form| form.saving) {
+            self.form = None;
+        }
+    }
+
+    fn on_enter(&mut self, ctx: &mut Ctx) {
+        if ctx.app.selected_project.as_ref().is_none_or(|p| p.id != self.id) {
+            ctx.app.selected_project = None;
+            ctx.dispatch(Action::FetchProjectDetail(self.id.clone()));
+        }
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App) {
     if let Some(project) = &app.selected_project {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -17,7 +354,6 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 Constraint::Length(6),  // Networking
                 Constraint::Min(8),     // Env vars
                 Constraint::Length(5),  // Webhook
-                Constraint::Length(3),  // Footer
             ])
             .split(area);
 
@@ -127,36 +463,24 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             Line::from(vec![
                 Span::styled("Webhook URL: ", Style::default().add_modifier(Modifier::BOLD)),
             ]),
-            Line::from(vec![
-                Span::styled(
-                    format!("http://localhost:8080/projects/{}/webhook/{}", 
-                        project.id, project.webhook_secret),
-                    Style::default().fg(Color::Yellow),
-                ),
-            ]),
+            // The secret isn't kept in the offline cache
+            if project.webhook_secret.is_empty() {
+                Line::from(Span::styled("Shown when online", Style::default().fg(Color::DarkGray)))
+            } else {
+                Line::from(vec![
+                    Span::styled(
+                        format!("http://localhost:8080/projects/{}/webhook/{}",
+                            project.id, project.webhook_secret),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                ])
+            },
         ];
 
         let webhook_widget = Paragraph::new(webhook_info)
             .block(Block::default().borders(Borders::ALL).title("Webhook Integration"))
             .wrap(Wrap { trim: true });
         f.render_widget(webhook_widget, chunks[4]);
-
-        // Footer
-        let footer_text = vec![
-            Line::from(vec![
-                Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-                Span::raw(" Back | "),
-                Span::styled("e", Style::default().fg(Color::Yellow)),
-                Span::raw(" Edit (Web UI) | "),
-                Span::styled("q", Style::default().fg(Color::Yellow)),
-                Span::raw(" Quit"),
-            ]),
-        ];
-
-        let footer = Paragraph::new(footer_text)
-            .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::DarkGray));
-        f.render_widget(footer, chunks[5]);
     } else {
         let loading = Paragraph::new("Loading project settings...")
             .block(Block::default().borders(Borders::ALL).title("Settings"))
@@ -164,3 +488,96 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         f.render_widget(loading, area);
     }
 }
+
+fn render_form(f: &mut Frame, area: Rect, form: &mut SettingsForm) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+    let mut constraints = vec![Constraint::Length(3)]; // Title
+    constraints.extend([Constraint::Length(3); FIELDS]);
+    constraints.push(Constraint::Min(0)); // Error
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(columns[0]);
+
+    let title = Paragraph::new(format!("Edit {} (port {} can't be changed)", form.original.name, form.port))
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let labels = [
+        "Project Name",
+        "Git Repository URL",
+        "Git Token (empty keeps the current one)",
+        "Runtime (←/→ to change)",
+        "Install Command (empty for the default)",
+        "Build Command (empty for the default)",
+        "Start Command (empty for the default)",
+    ];
+    for (i, label) in labels.iter().enumerate() {
+        let focused = form.focused_field == i && !form.reviewing;
+        let style = if focused { Style::default().fg(Color::Yellow) } else { Style::default() };
+        let block = Block::default().borders(Borders::ALL).title(*label).border_style(style);
+        let text = match i {
+            GIT_TOKEN => "•".repeat(form.inputs[i].value().chars().count()),
+            RUNTIME => format!("◂ {} ▸", if form.runtime.is_empty() { "auto" } else { &form.runtime }),
+            _ => form.inputs[i].value().to_string(),
+        };
+        f.render_widget(Paragraph::new(text).block(block), chunks[i + 1]);
+        if focused && i != RUNTIME {
+            let inner = chunks[i + 1];
+            let cursor = (form.inputs[i].visual_cursor() as u16).min(inner.width.saturating_sub(3));
+            f.set_cursor_position((inner.x + 1 + cursor, inner.y + 1));
+        }
+    }
+    form.field_areas.copy_from_slice(&chunks[1..=FIELDS]);
+
+    if let Some(error) = &form.error {
+        let error_widget = Paragraph::new(error.as_str())
+            .style(Style::default().fg(Color::Red))
+            .block(Block::default().borders(Borders::ALL).title("Error"))
+            .wrap(Wrap { trim: true });
+        f.render_widget(error_widget, chunks[FIELDS + 1]);
+    }
+
+    // What applying would change, old values in red and new ones in green
+    let changes = form.changes();
+    let mut lines = Vec::new();
+    for (label, old, new) in &changes {
+        lines.push(Line::from(Span::styled(*label, Style::default().add_modifier(Modifier::BOLD))));
+        lines.push(Line::from(Span::styled(format!("- {}", old), Style::default().fg(Color::Red))));
+        lines.push(Line::from(Span::styled(format!("+ {}", new), Style::default().fg(Color::Green))));
+    }
+    if changes.is_empty() {
+        lines.push(Line::from(Span::styled("No changes yet", Style::default().fg(Color::DarkGray))));
+    } else {
+        lines.push(Line::from(""));
+        let note = if form.saving {
+            "Saving..."
+        } else {
+            "Builds use the new settings from the next deploy on."
+        };
+        lines.push(Line::from(Span::styled(note, Style::default().fg(Color::DarkGray))));
+        if form.renamed() && form.live {
+            lines.push(Line::from(""));
+            let warning = format!(
+                "The container is named after the project. Apply and redeploy stops the one running as {} first; \
+                 just applying leaves it running, holding port {}, where Stop can't reach it.",
+                form.original.name, form.port,
+            );
+            lines.push(Line::from(Span::styled(warning, Style::default().fg(Color::Yellow))));
+        }
+    }
+    let (title, border) = if form.reviewing {
+        ("Apply these changes?", Style::default().fg(Color::Yellow))
+    } else {
+        ("Pending changes", Style::default())
+    };
+    let diff = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title).border_style(border))
+        .wrap(Wrap { trim: false });
+    f.render_widget(diff, columns[1]);
+}
@@ -1,16 +1,116 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
-use crate::ui::app::App;
+use anyhow::Result;
+use crossterm::event::Event;
+use crate::ui::action::Action;
+use crate::ui::app::{App, Screen};
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::list_filter::{ListFilter, SortKey};
 
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+const SORTS: &[SortKey] = &[SortKey::Name, SortKey::Newest, SortKey::Status, SortKey::Port];
+
+pub struct ProjectsView {
+    filter: ListFilter,
+}
+
+impl ProjectsView {
+    pub fn new() -> Self {
+        Self { filter: ListFilter::new(SORTS) }
+    }
+}
+
+impl Component for ProjectsView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        render(f, area, app, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let app = &mut *ctx.app;
+        let rows = self.filter.apply(&app.projects, app);
+        let action = match event {
+            Event::Key(key) => {
+                if self.filter.handle_key(key, &app.keys) {
+                    return Ok(Transition::Stay);
+                }
+                app.keys.action(Scope::Projects, key)
+            }
+            Event::Mouse(mouse) => self.filter.handle_mouse(mouse, &rows),
+            _ => None,
+        };
+        let Some(action) = action else {
+            return Ok(Transition::Stay);
+        };
+
+        if self.filter.navigate(action, &rows) {
+            return Ok(Transition::Stay);
+        }
+        let transition = match action {
+            KeyAction::Open => match self.filter.selected(&rows) {
+                Some(project) => Transition::Open(Screen::ProjectDetail(project.id.clone())),
+                None => Transition::Stay,
+            },
+            KeyAction::New if app.require_online() => Transition::Open(Screen::CreateProject),
+            KeyAction::Refresh => {
+                ctx.dispatch(Action::FetchProjects);
+                Transition::Stay
+            }
+            KeyAction::Activity => Transition::Open(Screen::Activity),
+            KeyAction::Deployments => Transition::Open(Screen::Deployments),
+            KeyAction::Network => Transition::Open(Screen::Network),
+            KeyAction::Storage => Transition::Open(Screen::Storage),
+            KeyAction::Docs => Transition::Open(Screen::Docs),
+            KeyAction::Settings => Transition::Open(Screen::Settings),
+            _ => Transition::Stay,
+        };
+        Ok(transition)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Projects
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        if self.filter.is_editing() {
+            return self.filter.keymap(keys);
+        }
+        let mut hints = keys.pair_hint(Scope::Projects, KeyAction::Up, KeyAction::Down, "Navigate");
+        hints.extend(keys.hints(
+            Scope::Projects,
+            &[
+                KeyAction::Open,
+                KeyAction::New,
+                KeyAction::Deployments,
+                KeyAction::Network,
+                KeyAction::Activity,
+                KeyAction::Storage,
+                KeyAction::Docs,
+                KeyAction::Settings,
+                KeyAction::Refresh,
+            ],
+        ));
+        hints.extend(self.filter.keymap(keys));
+        hints.extend(keys.hints(Scope::Projects, &[KeyAction::Quit]));
+        hints
+    }
+
+    fn focus(&mut self, id: &str, _app: &App) {
+        self.filter.select(id.to_string());
+    }
+
+    fn is_editing(&self) -> bool {
+        self.filter.is_editing()
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App, view: &mut ProjectsView) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(4)])
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
         .split(area);
 
     // Header
@@ -26,8 +126,10 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(header, chunks[0]);
 
     // Projects list
-    let items: Vec<ListItem> = app
-        .projects
+    let list_area = view.filter.render(f, chunks[1]);
+    let rows = view.filter.apply(&app.projects, app);
+    let selected = view.filter.selected_index(&rows);
+    let items: Vec<ListItem> = rows
         .iter()
         .enumerate()
         .map(|(i, p)| {
@@ -60,7 +162,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 symbol, p.name, status, p.port
             );
 
-            let style = if i == app.selected_index {
+            let style = if i == selected {
                 Style::default()
                     .fg(color)
                     .add_modifier(Modifier::BOLD)
@@ -77,59 +179,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Projects ({})", app.projects.len()))
-        )
-        .highlight_symbol(">> ");
-
-    f.render_widget(list, chunks[1]);
-
-    // Footer with controls
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("↑↓/jk", Style::default().fg(Color::Yellow)),
-            Span::raw(" Navigate | "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(" Details | "),
-            Span::styled("n", Style::default().fg(Color::Yellow)),
-            Span::raw(" New | "),
-            Span::styled("d", Style::default().fg(Color::Yellow)),
-            Span::raw(" Deployments | "),
-            Span::styled("w", Style::default().fg(Color::Yellow)),
-            Span::raw(" Network"),
-        ]),
-        Line::from(vec![
-            Span::styled("a", Style::default().fg(Color::Yellow)),
-            Span::raw(" Activity | "),
-            Span::styled("t", Style::default().fg(Color::Yellow)),
-            Span::raw(" Storage | "),
-            Span::styled("h", Style::default().fg(Color::Yellow)),
-            Span::raw(" Help | "),
-            Span::styled("s", Style::default().fg(Color::Yellow)),
-            Span::raw(" Settings | "),
-            Span::styled("r", Style::default().fg(Color::Yellow)),
-            Span::raw(" Refresh | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::DarkGray));
-    f.render_widget(footer, chunks[2]);
-
-    // Show message or error
-    if let Some(error) = &app.error {
-        let error_area = Rect {
-            x: area.width / 4,
-            y: area.height / 2 - 2,
-            width: area.width / 2,
-            height: 5,
-        };
-        
-        let error_widget = Paragraph::new(error.as_str())
-            .style(Style::default().fg(Color::Red))
-            .block(Block::default().borders(Borders::ALL).title("Error"));
-        f.render_widget(error_widget, error_area);
-    }
+                .title(view.filter.title("Projects", rows.len(), app.projects.len()))
+        );
+
+    view.filter.render_list(f, list, list_area, Some(selected));
 }
@@ -2,74 +2,296 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
-use crate::config::Config;
+use anyhow::Result;
+use crossterm::event::{Event, MouseEventKind};
+use crate::ui::confirm::{Answer, ConfirmDialog};
+use crate::api::ApiClient;
+use crate::cache;
+use crate::config::{delete_config, save_config};
+use crate::ui::action::Action;
+use crate::ui::App;
+use crate::ui::component::{select_next, select_previous, Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::mouse::{ListArea, ListClick};
 
-pub fn render(f: &mut Frame, area: Rect, config: &Config) {
+/// Changes to the stored credentials, made once confirmed.
+enum ProfileChange {
+    Reconfigure,
+    Remove,
+}
+
+pub struct SettingsView {
+    selected: usize,
+    confirm: Option<(ConfirmDialog, ProfileChange)>,
+    list: ListArea,
+}
+
+impl SettingsView {
+    pub fn new(app: &App) -> Self {
+        // Start on the active profile
+        let selected = app
+            .config
+            .profiles
+            .keys()
+            .position(|name| *name == app.active_profile)
+            .unwrap_or(0);
+        Self {
+            selected,
+            confirm: None,
+            list: ListArea::default(),
+        }
+    }
+
+    /// Name of the highlighted profile.
+    fn selected_profile<'a>(&self, app: &'a App) -> Option<&'a str> {
+        app.config.profiles.keys().nth(self.selected).map(String::as_str)
+    }
+
+    fn run(&mut self, action: KeyAction, ctx: &mut Ctx) -> Result<Transition> {
+        let app = &mut *ctx.app;
+        match action {
+            KeyAction::Back => return Ok(Transition::Back),
+            KeyAction::Down => {
+                self.selected = select_next(self.selected, app.config.profiles.len());
+            }
+            KeyAction::Up => {
+                self.selected = select_previous(self.selected, app.config.profiles.len());
+            }
+            KeyAction::Top => self.selected = 0,
+            KeyAction::Bottom => self.selected = app.config.profiles.len().saturating_sub(1),
+            KeyAction::Open => {
+                // Switch to the highlighted profile, or reconnect when offline
+                let Some(name) = self.selected_profile(app).map(str::to_string) else {
+                    return Ok(Transition::Stay);
+                };
+                if name == app.active_profile && !app.offline {
+                    return Ok(Transition::Stay);
+                }
+                let profile = app.config.profiles[&name].clone();
+                let client = ApiClient::new(profile.server_url, profile.api_key)
+                    .with_policy(app.config.request.clone());
+                ctx.dispatch(Action::SwitchProfile { name, client });
+            }
+            KeyAction::Reconfigure => {
+                let dialog = ConfirmDialog::new(
+                    "Reconfigure profile",
+                    format!(
+                        "Replace the server URL and API key of profile {}? The TUI restarts into setup.",
+                        app.active_profile
+                    ),
+                );
+                self.confirm = Some((dialog, ProfileChange::Reconfigure));
+            }
+            KeyAction::RemoveProfile => {
+                let dialog = ConfirmDialog::new(
+                    "Remove profile",
+                    format!(
+                        "Remove profile {} and its API key? The key can't be recovered from here.",
+                        app.active_profile
+                    ),
+                )
+                .type_to_confirm(app.active_profile.clone());
+                self.confirm = Some((dialog, ProfileChange::Remove));
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
+
+impl Component for SettingsView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        render(f, area, app, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        let app = &mut *ctx.app;
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) if self.confirm.is_none() => {
+                let action = match mouse.kind {
+                    MouseEventKind::ScrollDown => KeyAction::Down,
+                    MouseEventKind::ScrollUp => KeyAction::Up,
+                    _ => match self.list.click(mouse, app.config.profiles.len()) {
+                        Some(ListClick::Select(row)) => {
+                            self.selected = row;
+                            return Ok(Transition::Stay);
+                        }
+                        Some(ListClick::Open(row)) => {
+                            self.selected = row;
+                            KeyAction::Open
+                        }
+                        None => return Ok(Transition::Stay),
+                    },
+                };
+                return self.run(action, ctx);
+            }
+            _ => return Ok(Transition::Stay),
+        };
+
+        if let Some((dialog, _)) = &mut self.confirm {
+            match dialog.handle_key(key) {
+                Answer::Pending => return Ok(Transition::Stay),
+                Answer::No => {
+                    self.confirm = None;
+                    return Ok(Transition::Stay);
+                }
+                Answer::Yes => {}
+            }
+            let Some((_, change)) = self.confirm.take() else {
+                return Ok(Transition::Stay);
+            };
+            return apply(change, app);
+        }
+
+        match app.keys.action(Scope::Settings, key) {
+            Some(action) => self.run(action, ctx),
+            None => Ok(Transition::Stay),
+        }
+    }
+
+    fn is_editing(&self) -> bool {
+        self.confirm.is_some()
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Settings
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        let mut hints = keys.pair_hint(Scope::Settings, KeyAction::Up, KeyAction::Down, "Select");
+        hints.extend(keys.hints(
+            Scope::Settings,
+            &[
+                KeyAction::Open,
+                KeyAction::Reconfigure,
+                KeyAction::RemoveProfile,
+                KeyAction::Back,
+                KeyAction::Quit,
+            ],
+        ));
+        hints
+    }
+}
+
+fn apply(change: ProfileChange, app: &mut App) -> Result<Transition> {
+    match change {
+        ProfileChange::Reconfigure => app.reconfigure = true,
+        ProfileChange::Remove => {
+            // Remove the active profile and quit
+            app.config.remove_profile(&app.active_profile.clone())?;
+            if app.config.profiles.is_empty() {
+                delete_config()?;
+            } else {
+                save_config(&app.config)?;
+            }
+            let _ = cache::delete(&app.active_profile);
+        }
+    }
+    Ok(Transition::Quit)
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App, view: &mut SettingsView) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
-            Constraint::Length(10),
-            Constraint::Min(0),
-            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(9),
         ])
         .split(area);
 
     // Header
-    let header = Paragraph::new("CLI Configuration")
+    let header = Paragraph::new(format!("CLI Configuration - profile: {}", app.active_profile))
         .block(Block::default().borders(Borders::ALL).title("Settings"))
         .style(Style::default().fg(Color::Cyan));
     f.render_widget(header, chunks[0]);
 
-    // Config display
-    let masked_key = if config.api_key.len() > 10 {
-        format!("{}...{}", &config.api_key[..4], &config.api_key[config.api_key.len()-4..])
-    } else {
-        "••••••••".to_string()
-    };
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(chunks[1]);
 
-    let config_text = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Server URL: ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::styled(&config.server_url, Style::default().fg(Color::Cyan)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("API Key: ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::styled(masked_key, Style::default().fg(Color::Yellow)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Config File: ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw("~/.config/clickploy/config.toml"),
-        ]),
-    ];
+    // Profile list
+    let items: Vec<ListItem> = app
+        .config
+        .profiles
+        .keys()
+        .map(|name| {
+            let (marker, style) = if *name == app.active_profile {
+                ("* ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+            } else {
+                ("  ", Style::default())
+            };
+            ListItem::new(Line::from(Span::styled(format!("{}{}", marker, name), style)))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Profiles"))
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+
+    let mut state = ListState::default();
+    state.select(Some(view.selected));
+    f.render_stateful_widget(list, body[0], &mut state);
+    view.list.set(body[0], state.offset());
+
+    // Details of the highlighted profile
+    let mut config_text = vec![Line::from("")];
+    if let Some(profile) = view.selected_profile(app).and_then(|name| app.config.profiles.get(name)) {
+        let masked_key = if profile.api_key.len() > 10 {
+            format!("{}...{}", &profile.api_key[..4], &profile.api_key[profile.api_key.len()-4..])
+        } else {
+            "••••••••".to_string()
+        };
+
+        config_text.extend([
+            Line::from(vec![
+                Span::styled("Server URL: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(profile.server_url.clone(), Style::default().fg(Color::Cyan)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("API Key: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(masked_key, Style::default().fg(Color::Yellow)),
+            ]),
+            Line::from(""),
+        ]);
+    }
+    config_text.push(Line::from(vec![
+        Span::styled("Config File: ", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw("~/.config/clickploy/config.toml"),
+    ]));
 
     let config_display = Paragraph::new(config_text)
-        .block(Block::default().borders(Borders::ALL).title("Current Configuration"))
+        .block(Block::default().borders(Borders::ALL).title("Profile"))
         .wrap(Wrap { trim: true });
-    f.render_widget(config_display, chunks[1]);
+    f.render_widget(config_display, body[1]);
 
     // Actions
+    let key = |action| app.keys.key(Scope::Settings, action).unwrap_or_default();
     let actions_text = vec![
-        Line::from(""),
-        Line::from(Span::styled(
-            "Available Actions:",
-            Style::default().add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
         Line::from(vec![
-            Span::styled("c", Style::default().fg(Color::Yellow)),
-            Span::raw(" - Reconfigure (change server URL and API key)"),
+            Span::styled(key(KeyAction::Open), Style::default().fg(Color::Yellow)),
+            Span::raw(" - Switch to the highlighted profile"),
+        ]),
+        Line::from(vec![
+            Span::styled(key(KeyAction::Reconfigure), Style::default().fg(Color::Yellow)),
+            Span::raw(" - Reconfigure the active profile (server URL and API key)"),
         ]),
         Line::from(vec![
-            Span::styled("d", Style::default().fg(Color::Red)),
-            Span::raw(" - Delete configuration (logout)"),
+            Span::styled(key(KeyAction::RemoveProfile), Style::default().fg(Color::Red)),
+            Span::raw(" - Remove the active profile (logout)"),
         ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Add profiles with `clickploy context add <name> --server <url> --api-key <key>`",
+            Style::default().fg(Color::DarkGray),
+        )),
     ];
 
     let actions = Paragraph::new(actions_text)
@@ -77,18 +299,7 @@ pub fn render(f: &mut Frame, area: Rect, config: &Config) {
         .wrap(Wrap { trim: true });
     f.render_widget(actions, chunks[2]);
 
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-            Span::raw(" Back | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::DarkGray));
-    f.render_widget(footer, chunks[3]);
+    if let Some((dialog, _)) = &view.confirm {
+        dialog.render(f, area);
+    }
 }
@@ -5,13 +5,18 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
+use crossterm::event::MouseEvent;
 use tui_input::Input;
 
+use crate::ui::mouse::contains;
+
 pub struct SetupState {
     pub server_url: Input,
     pub api_key: Input,
     pub focused_field: usize,
     pub error: Option<String>,
+    /// Where the URL and API key fields were drawn.
+    field_areas: [Rect; 2],
 }
 
 impl SetupState {
@@ -23,6 +28,7 @@ impl SetupState {
             api_key: Input::default(),
             focused_field: 0,
             error: None,
+            field_areas: [Rect::default(); 2],
         }
     }
 
@@ -30,6 +36,11 @@ impl SetupState {
         self.focused_field = (self.focused_field + 1) % 2;
     }
 
+    /// The field under the pointer.
+    pub fn field_at(&self, mouse: &MouseEvent) -> Option<usize> {
+        self.field_areas.iter().position(|area| contains(*area, mouse))
+    }
+
     pub fn previous_field(&mut self) {
         if self.focused_field == 0 {
             self.focused_field = 1;
@@ -39,7 +50,7 @@ impl SetupState {
     }
 }
 
-pub fn render(f: &mut Frame, area: Rect, state: &SetupState) {
+pub fn render(f: &mut Frame, area: Rect, state: &mut SetupState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -101,6 +112,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &SetupState) {
 
     let api_key_input = Paragraph::new(masked_key).block(api_key_block);
     f.render_widget(api_key_input, chunks[2]);
+    state.field_areas = [chunks[1], chunks[2]];
 
     // Instructions
     let instructions = Paragraph::new(vec![
@@ -119,7 +131,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &SetupState) {
         Line::from("3. Paste it into the API Key field above"),
         Line::from(""),
         Line::from(Span::styled(
-            "Press Enter on URL to open browser | Tab to switch | Esc to quit",
+            "Press Enter on URL to open browser | Tab or click to switch | Esc to quit",
             Style::default().fg(Color::DarkGray),
         )),
     ])
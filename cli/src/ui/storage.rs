@@ -5,11 +5,155 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, List, ListItem, Gauge},
     Frame,
 };
-use crate::ui::app::App;
+use anyhow::Result;
+use crossterm::event::Event;
+use crate::models::{Database, DatabaseCredentials};
+use crate::ui::action::{Action, ActionResult};
+use crate::ui::app::{App, Screen};
+use crate::ui::component::{Component, Ctx, KeyHint, Transition};
+use crate::ui::keymap::{KeyAction, Keymap, Scope};
+use crate::ui::list_filter::{ListFilter, SortKey};
 
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
-    if app.show_db_credentials {
-        render_db_credentials(f, area, app);
+const SORTS: &[SortKey] = &[SortKey::Name, SortKey::Status, SortKey::Port];
+
+pub struct StorageView {
+    filter: ListFilter,
+    /// The database whose credentials are shown instead of the list.
+    viewing: Option<Database>,
+    credentials: Option<DatabaseCredentials>,
+}
+
+impl StorageView {
+    pub fn new() -> Self {
+        Self {
+            filter: ListFilter::new(SORTS),
+            viewing: None,
+            credentials: None,
+        }
+    }
+}
+
+impl Component for StorageView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app: &App) {
+        render(f, area, app, self);
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut Ctx) -> Result<Transition> {
+        if self.viewing.is_some() {
+            if let Event::Key(key) = event
+                && ctx.app.keys.action(Scope::Storage, key) == Some(KeyAction::Back)
+            {
+                self.viewing = None;
+                self.credentials = None;
+            }
+            return Ok(Transition::Stay);
+        }
+
+        let rows = self.filter.apply(&ctx.app.databases, ctx.app);
+        let action = match event {
+            Event::Key(key) => {
+                if self.filter.handle_key(key, &ctx.app.keys) {
+                    return Ok(Transition::Stay);
+                }
+                ctx.app.keys.action(Scope::Storage, key)
+            }
+            Event::Mouse(mouse) => self.filter.handle_mouse(mouse, &rows),
+            _ => None,
+        };
+        let Some(action) = action else {
+            return Ok(Transition::Stay);
+        };
+
+        if self.filter.navigate(action, &rows) {
+            return Ok(Transition::Stay);
+        }
+        let db = self.filter.selected(&rows).cloned();
+        match action {
+            KeyAction::Back => return Ok(Transition::Back),
+            KeyAction::New if ctx.app.require_online() => {
+                return Ok(Transition::Open(Screen::CreateDatabase));
+            }
+            _ => {}
+        }
+
+        // The rest acts on the highlighted database
+        let Some(db) = db else {
+            return Ok(Transition::Stay);
+        };
+        match action {
+            KeyAction::Open if ctx.app.require_online() => {
+                if db.db_type == "mongodb" {
+                    ctx.dispatch(Action::FetchDatabaseCredentials(db.id));
+                }
+                self.viewing = Some(db);
+            }
+            KeyAction::Delete if ctx.app.require_online() => ctx.confirm(Action::DeleteDatabase(db.id)),
+            KeyAction::Stop if ctx.app.require_online() => ctx.confirm(Action::StopDatabase(db.id)),
+            KeyAction::Restart if ctx.app.require_online() => ctx.confirm(Action::RestartDatabase(db.id)),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::Storage
+    }
+
+    fn keymap(&self, keys: &Keymap) -> Vec<KeyHint> {
+        if self.viewing.is_some() {
+            return keys.hints(Scope::Storage, &[KeyAction::Back, KeyAction::Quit]);
+        }
+
+        if self.filter.is_editing() {
+            return self.filter.keymap(keys);
+        }
+        let mut hints = keys.pair_hint(Scope::Storage, KeyAction::Up, KeyAction::Down, "Navigate");
+        hints.extend(keys.hints(
+            Scope::Storage,
+            &[
+                KeyAction::Open,
+                KeyAction::New,
+                KeyAction::Delete,
+                KeyAction::Stop,
+                KeyAction::Restart,
+                KeyAction::Back,
+            ],
+        ));
+        hints.extend(self.filter.keymap(keys));
+        hints.extend(keys.hints(Scope::Storage, &[KeyAction::Quit]));
+        hints
+    }
+
+    fn is_editing(&self) -> bool {
+        self.viewing.is_none() && self.filter.is_editing()
+    }
+
+    fn on_enter(&mut self, ctx: &mut Ctx) {
+        ctx.dispatch(Action::FetchStorage);
+    }
+
+    fn focus(&mut self, id: &str, _app: &App) {
+        self.filter.select(id.to_string());
+        self.viewing = None;
+        self.credentials = None;
+    }
+
+    fn on_result(&mut self, result: &ActionResult, app: &mut App) -> Transition {
+        if let ActionResult::DatabaseCredentials(id, result) = result
+            && self.viewing.as_ref().map(|db| db.id) == Some(*id)
+        {
+            match result {
+                Ok(credentials) => self.credentials = Some(credentials.clone()),
+                Err(e) => app.notifications.error(e.describe("Failed to fetch credentials")),
+            }
+        }
+        Transition::Stay
+    }
+}
+
+fn render(f: &mut Frame, area: Rect, app: &App, view: &mut StorageView) {
+    if let Some(db) = &view.viewing {
+        render_db_credentials(f, area, db, view);
         return;
     }
 
@@ -19,7 +163,6 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             Constraint::Length(3),
             Constraint::Length(5),
             Constraint::Min(0),
-            Constraint::Length(3),
         ])
         .split(area);
 
@@ -78,7 +221,10 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             .style(Style::default());
         f.render_widget(empty, chunks[2]);
     } else {
-        let items: Vec<ListItem> = app.databases.iter().enumerate().map(|(i, db)| {
+        let list_area = view.filter.render(f, chunks[2]);
+        let rows = view.filter.apply(&app.databases, app);
+        let selected = view.filter.selected_index(&rows);
+        let items: Vec<ListItem> = rows.iter().enumerate().map(|(i, db)| {
             let status_color = match db.status.as_str() {
                 "running" => Color::Green,
                 "stopped" => Color::Yellow,
@@ -96,7 +242,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 ]),
             ];
 
-            let style = if i == app.selected_index {
+            let style = if i == selected {
                 Style::default().bg(Color::DarkGray)
             } else {
                 Style::default()
@@ -106,44 +252,17 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         }).collect();
 
         let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title("Databases"));
-        f.render_widget(list, chunks[2]);
+            .block(Block::default().borders(Borders::ALL).title(view.filter.title("Databases", rows.len(), app.databases.len())));
+        view.filter.render_list(f, list, list_area, Some(selected));
     }
-
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-            Span::raw(" Navigate | "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(" View Credentials | "),
-            Span::styled("n", Style::default().fg(Color::Yellow)),
-            Span::raw(" New Database | "),
-            Span::styled("d", Style::default().fg(Color::Yellow)),
-            Span::raw(" Delete | "),
-            Span::styled("s", Style::default().fg(Color::Yellow)),
-            Span::raw(" Stop | "),
-            Span::styled("r", Style::default().fg(Color::Yellow)),
-            Span::raw(" Restart | "),
-            Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-            Span::raw(" Back | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL));
-    f.render_widget(footer, chunks[3]);
 }
 
-fn render_db_credentials(f: &mut Frame, area: Rect, app: &App) {
+fn render_db_credentials(f: &mut Frame, area: Rect, db: &Database, view: &StorageView) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(0),
-            Constraint::Length(3),
         ])
         .split(area);
 
@@ -154,166 +273,68 @@ fn render_db_credentials(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(header, chunks[0]);
 
     // Credentials
-    if let Some(db) = &app.selected_database {
-        let content = if let Some(creds) = &app.db_credentials {
-            vec![
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("Database: ", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(&db.name),
-                ]),
-                Line::from(vec![
-                    Span::styled("Type: ", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(&db.db_type),
-                ]),
-                Line::from(vec![
-                    Span::styled("Status: ", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::styled(&db.status, Style::default().fg(if db.status == "running" { Color::Green } else { Color::Yellow })),
-                ]),
-                Line::from(vec![
-                    Span::styled("Port: ", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(format!("{}", db.port)),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("Username: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow)),
-                    Span::raw(&creds.username),
-                ]),
-                Line::from(vec![
-                    Span::styled("Password: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow)),
-                    Span::raw(&creds.password),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("Local URI:", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan)),
-                ]),
-                Line::from(creds.uri.clone()),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("Public URI:", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan)),
-                ]),
-                Line::from(creds.public_uri.clone()),
-            ]
-        } else if db.db_type == "sqlite" {
-            vec![
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("Database: ", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(&db.name),
-                ]),
-                Line::from(vec![
-                    Span::styled("Type: ", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(&db.db_type),
-                ]),
-                Line::from(""),
-                Line::from("SQLite databases are file-based and don't have credentials."),
-                Line::from(format!("Database file: data/user_dbs/{}.db", &db.name)),
-            ]
-        } else {
-            vec![
-                Line::from(""),
-                Line::from("Loading credentials..."),
-            ]
-        };
-
-        let paragraph = Paragraph::new(content)
-            .block(Block::default().borders(Borders::ALL).title("Details"));
-        f.render_widget(paragraph, chunks[1]);
-    }
-
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("Backspace/Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(" Back | "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" Quit"),
-        ]),
-    ];
-
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL));
-    f.render_widget(footer, chunks[2]);
-}
-
-pub fn render_create_database(f: &mut Frame, area: Rect, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ])
-        .split(area);
-
-    // Header
-    let header = Paragraph::new("Create New Database")
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
-    f.render_widget(header, chunks[0]);
-
-    // Form
-    let form_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(5),
-            Constraint::Min(0),
-        ])
-        .margin(1)
-        .split(chunks[1]);
-
-    // Name input
-    let name_style = if app.create_database_state.focused_field == 0 {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
-    };
-    let name_input = Paragraph::new(app.create_database_state.name.as_str())
-        .block(Block::default().borders(Borders::ALL).title("Name").border_style(name_style));
-    f.render_widget(name_input, form_chunks[0]);
-
-    // Type selection
-    let type_style = if app.create_database_state.focused_field == 1 {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
-    };
-    
-    let db_types = vec!["sqlite", "mongodb"];
-    let type_list: Vec<Line> = db_types.iter().map(|&t| {
-        if t == app.create_database_state.db_type {
+    let content = if let Some(creds) = &view.credentials {
+        vec![
+            Line::from(""),
             Line::from(vec![
-                Span::styled("▸ ", Style::default().fg(Color::Green)),
-                Span::styled(t, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-            ])
-        } else {
+                Span::styled("Database: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(&db.name),
+            ]),
             Line::from(vec![
-                Span::raw("  "),
-                Span::raw(t),
-            ])
-        }
-    }).collect();
-
-    let type_select = Paragraph::new(type_list)
-        .block(Block::default().borders(Borders::ALL).title("Type").border_style(type_style));
-    f.render_widget(type_select, form_chunks[1]);
-
-    // Footer
-    let footer_text = vec![
-        Line::from(vec![
-            Span::styled("Tab", Style::default().fg(Color::Yellow)),
-            Span::raw(" Switch Field | "),
-            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-            Span::raw(" Select Type | "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(" Create | "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(" Cancel"),
-        ]),
-    ];
+                Span::styled("Type: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(&db.db_type),
+            ]),
+            Line::from(vec![
+                Span::styled("Status: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(&db.status, Style::default().fg(if db.status == "running" { Color::Green } else { Color::Yellow })),
+            ]),
+            Line::from(vec![
+                Span::styled("Port: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{}", db.port)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Username: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow)),
+                Span::raw(&creds.username),
+            ]),
+            Line::from(vec![
+                Span::styled("Password: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow)),
+                Span::raw(&creds.password),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Local URI:", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan)),
+            ]),
+            Line::from(creds.uri.clone()),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Public URI:", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan)),
+            ]),
+            Line::from(creds.public_uri.clone()),
+        ]
+    } else if db.db_type == "sqlite" {
+        vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Database: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(&db.name),
+            ]),
+            Line::from(vec![
+                Span::styled("Type: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(&db.db_type),
+            ]),
+            Line::from(""),
+            Line::from("SQLite databases are file-based and don't have credentials."),
+            Line::from(format!("Database file: data/user_dbs/{}.db", &db.name)),
+        ]
+    } else {
+        vec![
+            Line::from(""),
+            Line::from("Loading credentials..."),
+        ]
+    };
 
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::ALL));
-    f.render_widget(footer, chunks[2]);
+    let paragraph = Paragraph::new(content)
+        .block(Block::default().borders(Borders::ALL).title("Details"));
+    f.render_widget(paragraph, chunks[1]);
 }